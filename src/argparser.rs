@@ -3,6 +3,10 @@ use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::http::HttpSettings;
+use crate::providers::alerts::AlertSeverity;
+use crate::providers::indices::IndexKind;
+
 /// CLI for geocoding/weather
 #[derive(Parser, Debug)]
 #[command(author, version, about)]
@@ -18,46 +22,141 @@ pub struct Argparser {
          default_value_t = chrono::Local::now().naive_local().date())]
     pub date: NaiveDate,
 
+    /// Last day of a range starting at DATE, one report per day
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pub until: Option<NaiveDate>,
+
     /// Optional country code
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub country_code: Option<String>,
 
     /// Latitude (required if toponym not provided)
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub latitude: Option<f64>,
 
     /// Longitude (required if toponym not provided)
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub longitude: Option<f64>,
+
+    /// Exit with code 9 when an alert at or above SEVERITY is in effect, any alert by default
+    #[arg(long, global = true, value_enum, value_name = "SEVERITY", num_args = 0..=1,
+          require_equals = true, default_missing_value = "unknown")]
+    pub fail_on_alert: Option<AlertSeverity>,
+
+    #[command(flatten)]
+    pub http: HttpSettings,
 }
 
 /// Subcommands
 #[derive(Subcommand, Debug)]
 pub enum Commands {
-    /// Configure provider
+    /// Configure a provider instance, created when it does not exist yet
     Configure {
-        #[arg(value_enum)]
-        provider_name: ProviderName,
+        /// Instance name, e.g. ow-work
+        name: String,
+        /// Provider type of a new instance, inferred when the name is a type
+        #[arg(long = "type", value_enum)]
+        kind: Option<ProviderKind>,
     },
-    /// Select provider (open-weather, weather-api)
+    /// Select the provider instance used for weather requests
     Select {
-        #[arg(value_enum)]
-        provider_name: ProviderName,
+        /// Instance name, or a type with a single instance
+        name: String,
+    },
+    /// List providers with their capabilities
+    Providers,
+    /// Show calls made with each configured api key against its limits
+    Quota,
+    /// Show weather alerts in effect at a location
+    Alerts {
+        /// Toponym (city, place name)
+        toponym: Option<String>,
+    },
+    /// Show current air quality and pollen at a location
+    Air {
+        /// Toponym (city, place name)
+        toponym: Option<String>,
+        /// Where the data comes from, the selected provider when it has air quality data
+        #[arg(long, value_enum)]
+        source: Option<AirSource>,
+    },
+    /// Show sunrise, sunset, twilight and moon phase at a location, computed offline
+    Sun {
+        /// Toponym (city, place name)
+        toponym: Option<String>,
+        /// Date (default today)
+        #[arg(value_parser = parse_date,
+             default_value_t = chrono::Local::now().naive_local().date())]
+        date: NaiveDate,
+    },
+    /// Show heat-stress and cold-exposure indices for a day at a location
+    Indices {
+        /// Toponym (city, place name)
+        toponym: Option<String>,
+        /// Date (default today)
+        #[arg(value_parser = parse_date,
+             default_value_t = chrono::Local::now().naive_local().date())]
+        date: NaiveDate,
+        /// Index to show, repeatable, every one in range by default
+        #[arg(long = "index", value_enum, value_name = "INDEX")]
+        indices: Vec<IndexKind>,
+        /// Print the indices as JSON
+        #[arg(long)]
+        json: bool,
     },
 }
 
-/// Supported provider names
-#[derive(Debug, Clone, ValueEnum, Serialize, Deserialize, PartialEq, Eq, Hash)]
-pub enum ProviderName {
+/// Sources for `weather air`
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum AirSource {
+    /// The selected provider
+    Provider,
+    /// Open-Meteo air quality, keyless, with pollen in Europe
+    OpenMeteo,
+}
+
+/// Supported provider types, each configurable as any number of named instances
+#[derive(Debug, Clone, Copy, ValueEnum, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ProviderKind {
+    #[value(alias = "openweather")]
     OpenWeather,
+    #[value(alias = "weatherapi")]
     WeatherApi,
+    /// Offline canned or synthetic data
+    Fixture,
+    /// External command speaking the plugin protocol
+    Plugin,
+    /// REST source described entirely in config
+    #[value(alias = "jsonapi")]
+    JsonApi,
+    /// MET Norway Locationforecast, keyless
+    #[value(alias = "metno")]
+    MetNorway,
+    /// US National Weather Service, keyless
+    Nws,
+    /// Visual Crossing Timeline, long history and climate normals
+    #[value(alias = "visualcrossing")]
+    VisualCrossing,
+}
+
+impl ProviderKind {
+    /// Type named by `name`, e.g. `open-weather`, `openweather` or `OpenWeather`.
+    pub fn parse(name: &str) -> Option<Self> {
+        <Self as ValueEnum>::from_str(name, true).ok()
+    }
 }
 
-impl fmt::Display for ProviderName {
+impl fmt::Display for ProviderKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = match self {
-            ProviderName::WeatherApi => "WeatherApi",
-            ProviderName::OpenWeather => "OpenWeather",
+            ProviderKind::WeatherApi => "WeatherApi",
+            ProviderKind::OpenWeather => "OpenWeather",
+            ProviderKind::Fixture => "Fixture",
+            ProviderKind::Plugin => "Plugin",
+            ProviderKind::JsonApi => "JsonApi",
+            ProviderKind::MetNorway => "MetNorway",
+            ProviderKind::Nws => "Nws",
+            ProviderKind::VisualCrossing => "VisualCrossing",
         };
         write!(f, "{}", name)
     }
@@ -84,8 +183,30 @@ mod tests {
     }
 
     #[test]
-    fn test_provider_name_display() {
-        assert_eq!(format!("{}", ProviderName::WeatherApi), "WeatherApi");
-        assert_eq!(format!("{}", ProviderName::OpenWeather), "OpenWeather");
+    fn test_provider_kind_display() {
+        assert_eq!(format!("{}", ProviderKind::WeatherApi), "WeatherApi");
+        assert_eq!(format!("{}", ProviderKind::OpenWeather), "OpenWeather");
+        assert_eq!(format!("{}", ProviderKind::Fixture), "Fixture");
+        assert_eq!(format!("{}", ProviderKind::Plugin), "Plugin");
+        assert_eq!(format!("{}", ProviderKind::JsonApi), "JsonApi");
+        assert_eq!(format!("{}", ProviderKind::MetNorway), "MetNorway");
+        assert_eq!(format!("{}", ProviderKind::Nws), "Nws");
+        assert_eq!(
+            format!("{}", ProviderKind::VisualCrossing),
+            "VisualCrossing"
+        );
+    }
+
+    #[test]
+    fn test_provider_kind_parse() {
+        assert_eq!(
+            ProviderKind::parse("open-weather"),
+            Some(ProviderKind::OpenWeather)
+        );
+        assert_eq!(
+            ProviderKind::parse("WeatherApi"),
+            Some(ProviderKind::WeatherApi)
+        );
+        assert_eq!(ProviderKind::parse("ow-work"), None);
     }
 }
@@ -0,0 +1,425 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use std::fmt;
+
+/// Zenith distances of the sun's centre, in degrees, for each event.
+const SUNRISE: f64 = 90.833;
+const CIVIL: f64 = 96.;
+const NAUTICAL: f64 = 102.;
+const ASTRONOMICAL: f64 = 108.;
+
+/// Beginning and end of a daily period, either missing when it doesn't happen that day
+/// (polar day or night, or a moon that stays up).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub begin: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunTimes {
+    pub solar_noon: DateTime<Utc>,
+    /// Sunrise to sunset.
+    pub daylight: Span,
+    pub civil_twilight: Span,
+    pub nautical_twilight: Span,
+    pub astronomical_twilight: Span,
+    pub day_length: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoonPhase {
+    NewMoon,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    FullMoon,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl fmt::Display for MoonPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::NewMoon => "New Moon",
+            Self::WaxingCrescent => "Waxing Crescent",
+            Self::FirstQuarter => "First Quarter",
+            Self::WaxingGibbous => "Waxing Gibbous",
+            Self::FullMoon => "Full Moon",
+            Self::WaningGibbous => "Waning Gibbous",
+            Self::LastQuarter => "Last Quarter",
+            Self::WaningCrescent => "Waning Crescent",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoonTimes {
+    /// Fraction of the lunation at local solar noon, 0 new, 0.25 first quarter, 0.5 full
+    /// and 0.75 last quarter, as OpenWeather's `moon_phase`.
+    pub phase: f64,
+    /// Illuminated fraction of the disc at local solar noon, 0 to 1.
+    pub illumination: f64,
+    /// Moonrise and moonset, whichever happen during the day.
+    pub rise: Option<DateTime<Utc>>,
+    pub set: Option<DateTime<Utc>>,
+}
+
+impl MoonTimes {
+    pub fn phase_name(&self) -> MoonPhase {
+        let phases = [
+            MoonPhase::NewMoon,
+            MoonPhase::WaxingCrescent,
+            MoonPhase::FirstQuarter,
+            MoonPhase::WaxingGibbous,
+            MoonPhase::FullMoon,
+            MoonPhase::WaningGibbous,
+            MoonPhase::LastQuarter,
+            MoonPhase::WaningCrescent,
+        ];
+        phases[((self.phase * 8.).round() as usize) % 8]
+    }
+}
+
+/// Sun and moon events of one day at one place, computed offline.
+///
+/// The day is the location's solar day, centred on its solar noon, so events belong to
+/// the place's own date wherever the CLI runs. Times print in the system's time zone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Astronomy {
+    pub date: NaiveDate,
+    pub sun: SunTimes,
+    pub moon: MoonTimes,
+}
+
+impl Astronomy {
+    pub fn compute(latitude: f64, longitude: f64, date: NaiveDate) -> Self {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        // midnight at the location's mean solar time
+        let start = midnight - minutes(4. * longitude);
+        let sun = sun_times(latitude, longitude, midnight);
+        let (_, _, _, phase, illumination) = moon_position(julian_day(sun.solar_noon));
+        let (rise, set) = moon_crossings(latitude, longitude, start);
+        Self {
+            date,
+            sun,
+            moon: MoonTimes {
+                phase,
+                illumination,
+                rise,
+                set,
+            },
+        }
+    }
+}
+
+fn minutes(value: f64) -> Duration {
+    Duration::milliseconds((value * 60000.).round() as i64)
+}
+
+fn julian_day(at: DateTime<Utc>) -> f64 {
+    at.timestamp_millis() as f64 / 86400000. + 2440587.5
+}
+
+fn centuries(jd: f64) -> f64 {
+    (jd - 2451545.) / 36525.
+}
+
+/// Declination in radians and equation of time in minutes (NOAA solar equations).
+fn sun_position(jd: f64) -> (f64, f64) {
+    let t = centuries(jd);
+    let l0 = (280.46646 + t * (36000.76983 + t * 0.0003032)).rem_euclid(360.);
+    let m = 357.52911 + t * (35999.05029 - 0.0001537 * t);
+    let e = 0.016708634 - t * (0.000042037 + 0.0000001267 * t);
+    let (m_rad, l0_rad) = (m.to_radians(), l0.to_radians());
+    let center = m_rad.sin() * (1.914602 - t * (0.004817 + 0.000014 * t))
+        + (2. * m_rad).sin() * (0.019993 - 0.000101 * t)
+        + (3. * m_rad).sin() * 0.000289;
+    let omega = (125.04 - 1934.136 * t).to_radians();
+    let lambda = (l0 + center - 0.00569 - 0.00478 * omega.sin()).to_radians();
+    let mean_obliquity =
+        23. + (26. + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.) / 60.;
+    let obliquity = (mean_obliquity + 0.00256 * omega.cos()).to_radians();
+    let declination = (obliquity.sin() * lambda.sin()).asin();
+    let y = (obliquity / 2.).tan().powi(2);
+    let equation = y * (2. * l0_rad).sin() - 2. * e * m_rad.sin()
+        + 4. * e * y * m_rad.sin() * (2. * l0_rad).cos()
+        - 0.5 * y * y * (4. * l0_rad).sin()
+        - 1.25 * e * e * (2. * m_rad).sin();
+    (declination, 4. * equation.to_degrees())
+}
+
+/// Hour angle in degrees at which the sun's centre reaches `zenith`, `Err(true)` when it
+/// stays above that all day and `Err(false)` when it never gets there.
+fn hour_angle(latitude: f64, declination: f64, zenith: f64) -> Result<f64, bool> {
+    let lat = latitude.to_radians();
+    let cos_h = (zenith.to_radians().cos() - lat.sin() * declination.sin())
+        / (lat.cos() * declination.cos());
+    match cos_h {
+        c if c < -1. => Err(true),
+        c if c > 1. => Err(false),
+        c => Ok(c.acos().to_degrees()),
+    }
+}
+
+fn sun_times(latitude: f64, longitude: f64, midnight: DateTime<Utc>) -> SunTimes {
+    let noon_at = |estimate: DateTime<Utc>| {
+        let (_, equation) = sun_position(julian_day(estimate));
+        midnight + minutes(720. - 4. * longitude - equation)
+    };
+    let solar_noon = noon_at(noon_at(midnight + minutes(720. - 4. * longitude)));
+    // each event is refined once with the sun's position at its first estimate
+    let event = |zenith: f64, sign: f64| {
+        let mut at = solar_noon;
+        for _ in 0..2 {
+            let (declination, equation) = sun_position(julian_day(at));
+            let angle = hour_angle(latitude, declination, zenith).ok()?;
+            at = midnight + minutes(720. - 4. * longitude - equation + sign * 4. * angle);
+        }
+        Some(at)
+    };
+    let span = |zenith: f64| Span {
+        begin: event(zenith, -1.),
+        end: event(zenith, 1.),
+    };
+    let daylight = span(SUNRISE);
+    let day_length = match (daylight.begin, daylight.end) {
+        (Some(rise), Some(set)) => set - rise,
+        _ => {
+            let (declination, _) = sun_position(julian_day(solar_noon));
+            match hour_angle(latitude, declination, SUNRISE) {
+                Err(true) => Duration::hours(24),
+                _ => Duration::zero(),
+            }
+        }
+    };
+    SunTimes {
+        solar_noon,
+        daylight,
+        civil_twilight: span(CIVIL),
+        nautical_twilight: span(NAUTICAL),
+        astronomical_twilight: span(ASTRONOMICAL),
+        day_length,
+    }
+}
+
+/// Geocentric right ascension, declination and horizontal parallax of the moon in radians,
+/// with the lunation fraction and illuminated fraction, from the Astronomical Almanac's
+/// low-precision series (about 0.3° in position).
+fn moon_position(jd: f64) -> (f64, f64, f64, f64, f64) {
+    let t = centuries(jd);
+    let sin = |deg: f64| deg.to_radians().sin();
+    let cos = |deg: f64| deg.to_radians().cos();
+    let longitude = 218.32 + 481267.881 * t + 6.29 * sin(135.0 + 477198.87 * t)
+        - 1.27 * sin(259.3 - 413335.36 * t)
+        + 0.66 * sin(235.7 + 890534.22 * t)
+        + 0.21 * sin(269.9 + 954397.74 * t)
+        - 0.19 * sin(357.5 + 35999.05 * t)
+        - 0.11 * sin(186.5 + 966404.03 * t);
+    let latitude = 5.13 * sin(93.3 + 483202.02 * t) + 0.28 * sin(228.2 + 960400.89 * t)
+        - 0.28 * sin(318.3 + 6003.15 * t)
+        - 0.17 * sin(217.6 - 407332.21 * t);
+    let parallax = 0.9508
+        + 0.0518 * cos(135.0 + 477198.87 * t)
+        + 0.0095 * cos(259.3 - 413335.36 * t)
+        + 0.0078 * cos(235.7 + 890534.22 * t)
+        + 0.0028 * cos(269.9 + 954397.74 * t);
+    let obliquity = (23.439 - 0.013 * t).to_radians();
+    let (lambda, beta) = (longitude.to_radians(), latitude.to_radians());
+    let right_ascension =
+        (lambda.sin() * obliquity.cos() - beta.tan() * obliquity.sin()).atan2(lambda.cos());
+    let declination =
+        (beta.sin() * obliquity.cos() + beta.cos() * obliquity.sin() * lambda.sin()).asin();
+
+    let sun_longitude = 280.466 + 36000.770 * t + 1.915 * sin(357.529 + 35999.050 * t);
+    let elongation = (longitude - sun_longitude).rem_euclid(360.);
+    let phase = elongation / 360.;
+    let illumination = (1. - beta.cos() * elongation.to_radians().cos()) / 2.;
+    (
+        right_ascension,
+        declination,
+        parallax.to_radians(),
+        phase,
+        illumination,
+    )
+}
+
+/// Moon altitude above its rise/set altitude, which allows for parallax, refraction
+/// and the semi-diameter; positive while the moon is up.
+fn moon_altitude(latitude: f64, longitude: f64, at: DateTime<Utc>) -> f64 {
+    let jd = julian_day(at);
+    let (right_ascension, declination, parallax, _, _) = moon_position(jd);
+    let sidereal = (280.46061837 + 360.98564736629 * (jd - 2451545.) + longitude).to_radians();
+    let hour_angle = sidereal - right_ascension;
+    let lat = latitude.to_radians();
+    let altitude =
+        (lat.sin() * declination.sin() + lat.cos() * declination.cos() * hour_angle.cos()).asin();
+    altitude - (0.7275 * parallax - (0.5667f64).to_radians())
+}
+
+/// First moonrise and moonset in the 24 hours from `start`, sampled every 10 minutes
+/// and narrowed to under a minute.
+fn moon_crossings(
+    latitude: f64,
+    longitude: f64,
+    start: DateTime<Utc>,
+) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let altitude = |at| moon_altitude(latitude, longitude, at);
+    let step = Duration::minutes(10);
+    let (mut rise, mut set) = (None, None);
+    let mut before = altitude(start);
+    for i in 1..=144 {
+        let (mut low, mut high) = (start + step * (i - 1), start + step * i);
+        let after = altitude(high);
+        if (before > 0.) != (after > 0.) {
+            let rising = after > 0.;
+            while high - low > Duration::seconds(30) {
+                let middle = low + (high - low) / 2;
+                if (altitude(middle) > 0.) == rising {
+                    high = middle;
+                } else {
+                    low = middle;
+                }
+            }
+            let slot = if rising { &mut rise } else { &mut set };
+            slot.get_or_insert(high);
+        }
+        before = after;
+    }
+    (rise, set)
+}
+
+fn clock(at: Option<DateTime<Utc>>) -> String {
+    at.map(|at| at.with_timezone(&Local).format("%H:%M").to_string())
+        .unwrap_or("-".to_string())
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", clock(self.begin), clock(self.end))
+    }
+}
+
+impl fmt::Display for Astronomy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sun = &self.sun;
+        let length = sun.day_length.num_minutes();
+        write!(
+            f,
+            "Sun           : rise {} | set {} | noon {} | day {}h{:02}m\n\
+            Twilight      : civil {} | nautical {} | astronomical {}\n\
+            Moon          : {} {:.0}% | rise {} | set {}",
+            clock(sun.daylight.begin),
+            clock(sun.daylight.end),
+            clock(Some(sun.solar_noon)),
+            length / 60,
+            length % 60,
+            sun.civil_twilight,
+            sun.nautical_twilight,
+            sun.astronomical_twilight,
+            self.moon.phase_name(),
+            self.moon.illumination * 100.,
+            clock(self.moon.rise),
+            clock(self.moon.set),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().to_utc()
+    }
+
+    fn assert_close(actual: Option<DateTime<Utc>>, expected: DateTime<Utc>, tolerance: i64) {
+        let actual = actual.expect("event expected");
+        assert!(
+            (actual - expected).num_minutes().abs() <= tolerance,
+            "{actual} is not within {tolerance} min of {expected}"
+        );
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_sun_matches_noaa() {
+        // NOAA solar calculator for Kyiv on 2023-05-01: sunrise 02:33:46 UTC (1682908426),
+        // sunset 17:17:21 UTC (1682961441), as the OpenWeather cassette records them
+        let sun = Astronomy::compute(50.4547, 30.5238, date(2023, 5, 1)).sun;
+        assert_close(sun.daylight.begin, at(1682908426), 1);
+        assert_close(sun.daylight.end, at(1682961441), 1);
+    }
+
+    #[test]
+    fn test_sun_and_moon_in_london() {
+        // London on 2023-05-01, local times in BST: sunrise 05:33, sunset 20:23 (NOAA),
+        // moonset 04:26, moonrise 15:27 and 82% lit at noon (Meeus' full lunar theory,
+        // chapter 47), Waxing Gibbous
+        let astronomy = Astronomy::compute(51.51, -0.13, date(2023, 5, 1));
+        assert_close(
+            astronomy.sun.daylight.begin,
+            utc("2023-05-01T05:33:02+01:00"),
+            1,
+        );
+        assert_close(
+            astronomy.sun.daylight.end,
+            utc("2023-05-01T20:23:19+01:00"),
+            1,
+        );
+        assert_close(astronomy.moon.rise, utc("2023-05-01T15:27:19+01:00"), 2);
+        assert_close(astronomy.moon.set, utc("2023-05-01T04:26:21+01:00"), 2);
+        assert_eq!(astronomy.moon.phase_name(), MoonPhase::WaxingGibbous);
+        assert!(
+            (astronomy.moon.illumination - 0.82).abs() < 0.01,
+            "{}",
+            astronomy.moon.illumination
+        );
+    }
+
+    #[test]
+    fn test_moon_phase_fraction() {
+        // full moon on 2023-05-05 17:34 UTC, new moon on 2023-05-19 15:53 UTC
+        let (_, _, _, full, lit) = moon_position(julian_day(utc("2023-05-05T17:34:00Z")));
+        assert!((full - 0.5).abs() < 0.01, "{full}");
+        assert!(lit > 0.99, "{lit}");
+        let (_, _, _, new, _) = moon_position(julian_day(utc("2023-05-19T15:53:00Z")));
+        assert!(!(0.01..=0.99).contains(&new), "{new}");
+    }
+
+    #[test]
+    fn test_solar_noon_and_twilight_order() {
+        let sun = Astronomy::compute(51.51, -0.13, date(2023, 5, 1)).sun;
+        assert_close(Some(sun.solar_noon), utc("2023-05-01T11:57:00Z"), 1);
+        let begins = [
+            sun.astronomical_twilight.begin,
+            sun.nautical_twilight.begin,
+            sun.civil_twilight.begin,
+            sun.daylight.begin,
+        ];
+        assert!(
+            begins.windows(2).all(|pair| pair[0] < pair[1]),
+            "{begins:?}"
+        );
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        let tromso = |d| Astronomy::compute(69.65, 18.96, d).sun;
+        let summer = tromso(date(2023, 6, 21));
+        assert_eq!(summer.daylight.begin, None);
+        assert_eq!(summer.day_length, Duration::hours(24));
+        let winter = tromso(date(2023, 12, 21));
+        assert_eq!(winter.daylight.end, None);
+        assert_eq!(winter.day_length, Duration::zero());
+        assert!(winter.civil_twilight.begin.is_some());
+    }
+}
@@ -0,0 +1,208 @@
+use reqwest::blocking::Response;
+use reqwest::header::RETRY_AFTER;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+use crate::redact::redact;
+
+const CASSETTE_FILE_NAME: &str = "cassette.json";
+
+#[derive(Error, Debug)]
+pub enum CassetteError {
+    #[error("failed to read cassette {0}: {1}")]
+    Read(PathBuf, String),
+    #[error("failed to write cassette {0}: {1}")]
+    Write(PathBuf, String),
+    #[error("no recorded {0} {1} left in cassette")]
+    Missing(String, String),
+}
+
+/// One recorded HTTP exchange, secrets already scrubbed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<String>,
+    pub body: String,
+}
+
+impl Interaction {
+    fn path(&self) -> &str {
+        request_path(&self.url)
+    }
+
+    pub fn into_response(self) -> Response {
+        let mut builder = ::http::Response::builder()
+            .status(self.status)
+            .header("content-type", "application/json");
+        if let Some(retry_after) = self.retry_after {
+            builder = builder.header(RETRY_AFTER, retry_after);
+        }
+        // status comes from a u16 already accepted by the server or a cassette
+        builder
+            .body(self.body)
+            .unwrap_or_else(|_| ::http::Response::new(String::new()))
+            .into()
+    }
+}
+
+/// HTTP exchanges kept in `<dir>/cassette.json`.
+///
+/// Recording appends every exchange, replaying serves them back in recorded order
+/// for each method and path. Query strings are not matched, so dates and keys in
+/// them do not tie a cassette to the day it was recorded.
+#[derive(Debug)]
+pub enum Cassette {
+    Record(PathBuf),
+    Replay(Mutex<Vec<Interaction>>),
+}
+
+impl Cassette {
+    /// Starts a new recording in `dir`, dropping an earlier one.
+    pub fn record(dir: &Path) -> Result<Self, CassetteError> {
+        let file = dir.join(CASSETTE_FILE_NAME);
+        fs::create_dir_all(dir).map_err(|e| CassetteError::Write(file.clone(), e.to_string()))?;
+        write_interactions(&file, &[])?;
+        Ok(Self::Record(file))
+    }
+
+    pub fn replay(dir: &Path) -> Result<Self, CassetteError> {
+        let file = dir.join(CASSETTE_FILE_NAME);
+        let contents = fs::read_to_string(&file)
+            .map_err(|e| CassetteError::Read(file.clone(), e.to_string()))?;
+        let interactions = serde_json::from_str(&contents)
+            .map_err(|e| CassetteError::Read(file.clone(), e.to_string()))?;
+        Ok(Self::Replay(Mutex::new(interactions)))
+    }
+
+    /// Takes the next recorded answer for `method` and `url`.
+    pub fn next(&self, method: &str, url: &str) -> Result<Interaction, CassetteError> {
+        let missing = || CassetteError::Missing(method.to_string(), redact(request_path(url)));
+        let Self::Replay(interactions) = self else {
+            return Err(missing());
+        };
+        let mut interactions = interactions.lock().unwrap_or_else(|e| e.into_inner());
+        let pos = interactions
+            .iter()
+            .position(|i| i.method == method && i.path() == request_path(url))
+            .ok_or_else(missing)?;
+        Ok(interactions.remove(pos))
+    }
+
+    /// Saves a live response and hands back an equivalent one, its body was consumed.
+    pub fn save(
+        &self,
+        method: &str,
+        url: &str,
+        response: Response,
+    ) -> Result<Response, CassetteError> {
+        let Self::Record(file) = self else {
+            return Ok(response);
+        };
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response
+            .text()
+            .map_err(|e| CassetteError::Write(file.clone(), e.without_url().to_string()))?;
+        let interaction = Interaction {
+            method: method.to_string(),
+            url: redact(url),
+            status,
+            retry_after,
+            body: redact(&body),
+        };
+        let contents = fs::read_to_string(file)
+            .map_err(|e| CassetteError::Read(file.clone(), e.to_string()))?;
+        let mut interactions: Vec<Interaction> = serde_json::from_str(&contents)
+            .map_err(|e| CassetteError::Read(file.clone(), e.to_string()))?;
+        interactions.push(interaction.clone());
+        write_interactions(file, &interactions)?;
+        // hand back the live body, the scrubbed copy is only for the file
+        Ok(Interaction {
+            body,
+            ..interaction
+        }
+        .into_response())
+    }
+}
+
+fn write_interactions(file: &Path, interactions: &[Interaction]) -> Result<(), CassetteError> {
+    let contents = serde_json::to_string_pretty(interactions)
+        .map_err(|e| CassetteError::Write(file.to_path_buf(), e.to_string()))?;
+    fs::write(file, contents).map_err(|e| CassetteError::Write(file.to_path_buf(), e.to_string()))
+}
+
+/// URL without scheme, host and query, e.g. `/v1/history.json`.
+fn request_path(url: &str) -> &str {
+    let without_query = url.split('?').next().unwrap_or_default();
+    let after_scheme = without_query
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(without_query);
+    after_scheme
+        .find('/')
+        .map(|pos| &after_scheme[pos..])
+        .unwrap_or("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interaction(url: &str, body: &str) -> Interaction {
+        Interaction {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            status: 200,
+            retry_after: None,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_request_path() {
+        assert_eq!(
+            request_path("https://api.weatherapi.com/v1/history.json?q=1&key=abc"),
+            "/v1/history.json"
+        );
+        assert_eq!(request_path("http://127.0.0.1:8080"), "/");
+    }
+
+    #[test]
+    fn test_replay_matches_path_in_order() {
+        let cassette = Cassette::Replay(Mutex::new(vec![
+            interaction("https://a.test/v1/search?name=x", "geo"),
+            interaction("https://b.test/v1/history.json?dt=2023-05-01", "first"),
+            interaction("https://b.test/v1/history.json?dt=2023-05-01", "second"),
+        ]));
+        let url = "https://b.test/v1/history.json?dt=2024-01-01&key=live";
+        assert_eq!(cassette.next("GET", url).unwrap().body, "first");
+        assert_eq!(cassette.next("GET", url).unwrap().body, "second");
+        assert!(matches!(
+            cassette.next("GET", url),
+            Err(CassetteError::Missing(..))
+        ));
+    }
+
+    #[test]
+    fn test_interaction_into_response() {
+        let recorded = Interaction {
+            retry_after: Some("30".to_string()),
+            status: 429,
+            ..interaction("https://a.test/x", "{}")
+        };
+        let response = recorded.into_response();
+        assert_eq!(response.status().as_u16(), 429);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "30");
+        assert_eq!(response.text().unwrap(), "{}");
+    }
+}
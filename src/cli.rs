@@ -1,36 +1,312 @@
+use crate::argparser::{AirSource, ProviderKind};
+use crate::astronomy::Astronomy;
 use crate::configs::Config;
 use crate::geocoder::{Geocoder, GeocoderError};
+use crate::http::{HttpClient, HttpError, HttpSettings};
 use crate::input::read_user_number;
 use crate::provider_builder::{ProviderBuilder, ProviderBuilderError};
+use crate::providers::WeatherProvider;
+use crate::providers::air_quality::AirQuality;
+use crate::providers::alerts::{AlertSeverity, WeatherAlert, at_least};
 use crate::providers::error::ProviderError;
+use crate::providers::indices::{Index, IndexKind};
+use crate::providers::open_meteo::OpenMeteoAir;
+use crate::quota::{QuotaReport, UsageFile, key_id};
+use crate::redact::redact;
 
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate, Utc};
+use clap::ValueEnum;
+use std::ops::RangeInclusive;
 use thiserror::Error;
 
 const POPULATION_THRESHOLD: u32 = 100_000;
 
 #[derive(Error, Debug)]
 pub enum WeatherCliError {
-    #[error("Geocoder failed with an error: {0}")]
+    #[error("Geocoder failed with an error: {}", redact(&.0.to_string()))]
     Geocoder(#[from] GeocoderError),
     #[error("Provider builder failed with an error: {0}")]
     ProviderBuilder(#[from] ProviderBuilderError),
-    #[error("Provider failed with an error: {0}")]
+    #[error("Provider failed with an error: {}", redact(&.0.to_string()))]
     ProviderError(#[from] ProviderError),
+    #[error("Http client setup failed with an error: {0}")]
+    Http(#[from] HttpError),
+    #[error("{0} alert(s) at or above {1} severity in effect")]
+    ActiveAlerts(usize, AlertSeverity),
+}
+
+impl WeatherCliError {
+    /// Process exit code, see README "Exit Codes".
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::ProviderBuilder(_) | Self::Http(_) => 3,
+            Self::ProviderError(e) => e.exit_code(),
+            Self::ActiveAlerts(..) => 9,
+            Self::Geocoder(GeocoderError::FailedRequest(_)) => 8,
+            Self::Geocoder(GeocoderError::GeocoderInternalError(_, status)) if *status >= 500 => 8,
+            Self::Geocoder(_) => 1,
+        }
+    }
 }
 
 pub fn run(
+    config: Config,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    toponym: Option<String>,
+    country_code: Option<String>,
+    date: NaiveDate,
+    http: HttpSettings,
+) -> Result<Vec<WeatherAlert>, WeatherCliError> {
+    let (provider, lat, lon) = prepare(
+        config,
+        latitude,
+        longitude,
+        toponym,
+        country_code,
+        &[date],
+        http,
+    )?;
+    let report = provider
+        .get_weather(lat, lon, date)?
+        .with_astronomy(Astronomy::compute(lat, lon, date));
+    println!("{}", report);
+    Ok(report.alerts().to_vec())
+}
+
+/// Prints a report for every day of `dates`, in one request where the provider allows it.
+/// Returns the alerts shown, each once. An inverted range fails before any request.
+pub fn run_range(
+    config: Config,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    toponym: Option<String>,
+    country_code: Option<String>,
+    dates: RangeInclusive<NaiveDate>,
+    http: HttpSettings,
+) -> Result<Vec<WeatherAlert>, WeatherCliError> {
+    let (start, end) = dates.into_inner();
+    if end < start {
+        return Err(ProviderError::DateIsOutOfRange(format!(
+            "--until {} is before {}",
+            end, start
+        ))
+        .into());
+    }
+    let (provider, lat, lon) = prepare(
+        config,
+        latitude,
+        longitude,
+        toponym,
+        country_code,
+        &[start, end],
+        http,
+    )?;
+    let reports = provider.get_weather_range(lat, lon, start, end)?;
+    let reports = reports.into_iter().map(|(date, report)| {
+        (
+            date,
+            report.with_astronomy(Astronomy::compute(lat, lon, date)),
+        )
+    });
+    let mut alerts: Vec<WeatherAlert> = Vec::new();
+    for (i, (date, report)) in reports.enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}\n{}", date, report);
+        for alert in report.alerts() {
+            if !alerts.contains(alert) {
+                alerts.push(alert.clone());
+            }
+        }
+    }
+    Ok(alerts)
+}
+
+/// Prints the alerts in effect at a location now, for `weather alerts`.
+pub fn show_alerts(
+    config: Config,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    toponym: Option<String>,
+    country_code: Option<String>,
+    http: HttpSettings,
+) -> Result<Vec<WeatherAlert>, WeatherCliError> {
+    let (provider, lat, lon) = prepare(
+        config,
+        latitude,
+        longitude,
+        toponym,
+        country_code,
+        &[],
+        http,
+    )?;
+    let alerts = provider.get_alerts(lat, lon)?;
+    if alerts.is_empty() {
+        println!("no alerts in effect");
+    }
+    for (i, alert) in alerts.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}", alert);
+    }
+    Ok(alerts)
+}
+
+/// Prints current air quality from `source`. Without one, the selected provider answers
+/// when it has air quality data and Open-Meteo otherwise.
+pub fn show_air(
+    mut config: Config,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    toponym: Option<String>,
+    country_code: Option<String>,
+    source: Option<AirSource>,
+    http: HttpSettings,
+) -> Result<AirQuality, WeatherCliError> {
+    config.http = config.http.merge(http);
+    let client = HttpClient::new(&config.http)?;
+    let geocoder = Geocoder::new(config.geocoder.base_url.clone(), client.clone());
+    let open_meteo = OpenMeteoAir::new(config.air_quality.base_url.clone(), client.clone());
+    let provider = match source {
+        Some(AirSource::Provider) => Some(ProviderBuilder::build_provider(config, client)?),
+        Some(AirSource::OpenMeteo) => None,
+        None => ProviderBuilder::build_provider(config, client)
+            .ok()
+            .filter(|provider| provider.capabilities().air_quality),
+    };
+    let (lat, lon) = locate(&geocoder, latitude, longitude, toponym, country_code)?;
+    let air = match provider {
+        Some(provider) => provider.get_air_quality(lat, lon)?,
+        None => open_meteo.get_air_quality(lat, lon)?,
+    };
+    println!("{}", air);
+    Ok(air)
+}
+
+/// Prints sunrise, sunset, twilight and the moon for `date`, for `weather sun`. Needs
+/// no provider, only the geocoder when the place is named.
+pub fn show_sun(
+    config: Config,
     latitude: Option<f64>,
     longitude: Option<f64>,
     toponym: Option<String>,
     country_code: Option<String>,
     date: NaiveDate,
+    http: HttpSettings,
+) -> Result<Astronomy, WeatherCliError> {
+    let client = HttpClient::new(&config.http.merge(http))?;
+    let geocoder = Geocoder::new(config.geocoder.base_url, client);
+    let (lat, lon) = locate(&geocoder, latitude, longitude, toponym, country_code)?;
+    let astronomy = Astronomy::compute(lat, lon, date);
+    println!("{}\n{}", date, astronomy);
+    Ok(astronomy)
+}
+
+/// What `weather indices` prints: the indices of `date` named, or all of them when
+/// `kinds` is empty, as text or as JSON.
+#[derive(Debug, Clone)]
+pub struct IndicesOptions {
+    pub date: NaiveDate,
+    pub kinds: Vec<IndexKind>,
+    pub json: bool,
+}
+
+/// Prints the heat-stress and cold-exposure indices of the day's report, for `weather
+/// indices`. Indices out of range for the day are left out.
+pub fn show_indices(
+    config: Config,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    toponym: Option<String>,
+    country_code: Option<String>,
+    options: IndicesOptions,
+    http: HttpSettings,
+) -> Result<Vec<Index>, WeatherCliError> {
+    let IndicesOptions { date, kinds, json } = options;
+    let (provider, lat, lon) = prepare(
+        config,
+        latitude,
+        longitude,
+        toponym,
+        country_code,
+        &[date],
+        http,
+    )?;
+    let indices: Vec<Index> = provider
+        .get_weather(lat, lon, date)?
+        .indices()
+        .into_iter()
+        .filter(|index| kinds.is_empty() || kinds.contains(&index.kind))
+        .collect();
+    if json {
+        let document = serde_json::json!({
+            "date": date,
+            "latitude": lat,
+            "longitude": lon,
+            "indices": indices,
+        });
+        println!("{:#}", document);
+    } else {
+        println!("{}", date);
+        if indices.is_empty() {
+            println!("no index in range for the day");
+        }
+        for index in &indices {
+            println!("{:<14}: {}", index.kind.name(), index);
+        }
+    }
+    Ok(indices)
+}
+
+/// Fails with `ActiveAlerts` when `threshold` is set and an alert reaches it.
+pub fn check_alerts(
+    alerts: &[WeatherAlert],
+    threshold: Option<AlertSeverity>,
 ) -> Result<(), WeatherCliError> {
-    let config = Config::load();
-    let provider = ProviderBuilder::build_provider(config)?;
-    let (lat, lon) = if toponym.is_some() {
-        let toponym = toponym.unwrap();
-        let mut geo = Geocoder::resolve_address(toponym.clone(), country_code)?;
+    let Some(threshold) = threshold else {
+        return Ok(());
+    };
+    match at_least(alerts, threshold) {
+        0 => Ok(()),
+        count => Err(WeatherCliError::ActiveAlerts(count, threshold)),
+    }
+}
+
+/// Builds the selected provider, checks `dates` against it and resolves the location.
+fn prepare(
+    mut config: Config,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    toponym: Option<String>,
+    country_code: Option<String>,
+    dates: &[NaiveDate],
+    http: HttpSettings,
+) -> Result<(Box<dyn WeatherProvider>, f64, f64), WeatherCliError> {
+    config.http = config.http.merge(http);
+    let client = HttpClient::new(&config.http)?;
+    let geocoder = Geocoder::new(config.geocoder.base_url.clone(), client.clone());
+    let provider = ProviderBuilder::build_provider(config, client.clone())?;
+    let today = Local::now().naive_local().date();
+    for date in dates {
+        provider.capabilities().check_date(*date, today)?;
+    }
+    let (lat, lon) = locate(&geocoder, latitude, longitude, toponym, country_code)?;
+    Ok((provider, lat, lon))
+}
+
+/// Coordinates given, or those of the best geocoder match for `toponym`.
+fn locate(
+    geocoder: &Geocoder,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    toponym: Option<String>,
+    country_code: Option<String>,
+) -> Result<(f64, f64), WeatherCliError> {
+    let location = if let Some(toponym) = toponym {
+        let mut geo = geocoder.resolve_address(toponym.clone(), country_code)?;
         let top = if geo.results.len() == 1 {
             geo.results.remove(0)
         } else if let Some(res) = geo
@@ -50,7 +326,59 @@ pub fn run(
     } else {
         (latitude.unwrap(), longitude.unwrap())
     };
-    let report = provider.get_weather(lat, lon, date)?;
-    println!("{}", report);
-    Ok(())
+    Ok(location)
+}
+
+pub fn list_providers() {
+    let config = Config::load();
+    let selected = config
+        .default_provider
+        .as_deref()
+        .and_then(|name| config.resolve(name))
+        .map(|(name, _)| name);
+    let mut names: Vec<_> = config.providers.keys().collect();
+    names.sort();
+    if names.is_empty() {
+        println!("no providers configured");
+    }
+    for name in names {
+        let provider_config = &config.providers[name];
+        let kind = provider_config.kind();
+        let selected = if selected == Some(name.as_str()) {
+            ", selected"
+        } else {
+            ""
+        };
+        println!("{} ({}{})", name, kind, selected);
+        println!("    {}", ProviderBuilder::capabilities_of(provider_config));
+    }
+    let kinds: Vec<_> = ProviderKind::value_variants()
+        .iter()
+        .filter_map(|kind| kind.to_possible_value())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    println!("types: {}", kinds.join(", "));
+}
+
+pub fn show_quota() {
+    let config = Config::load();
+    let usage = UsageFile::load();
+    let mut instances: Vec<_> = config.providers.iter().collect();
+    instances.sort_by_key(|(name, _)| name.as_str());
+    for (name, provider_config) in instances {
+        let limits = config.quota_limits(name, provider_config.kind());
+        for api_key in provider_config.api_keys() {
+            let key_id = key_id(&api_key.key);
+            println!(
+                "{}",
+                QuotaReport {
+                    provider: name,
+                    label: &api_key.label,
+                    key_id: &key_id,
+                    usage: &usage.usage(name, &key_id, Utc::now()),
+                    limits: &limits,
+                }
+            );
+        }
+    }
 }
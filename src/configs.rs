@@ -1,81 +1,504 @@
-use crate::argparser::ProviderName;
+use crate::argparser::ProviderKind;
+use crate::http::HttpSettings;
 use crate::input::read_user_string;
+use crate::providers::json_api::{JsonApiSpec, JsonFields};
+use crate::quota::QuotaLimits;
+use crate::redact;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::fs;
+use std::path::PathBuf;
+use thiserror::Error;
 
 const CONFIG_FILE_NAME: &str = "/tmp/weather_cli_config.toml";
 
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("no provider named '{0}' is configured, see `weather providers`")]
+    UnknownProvider(String),
+    #[error("'{0}' is a new provider, give its type with --type")]
+    MissingType(String),
+}
+
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Config {
-    pub default_provider: Option<ProviderName>,
-    pub providers: HashMap<ProviderName, ProviderConfig>,
+    /// Name of the selected provider instance.
+    pub default_provider: Option<String>,
+    /// Provider instances by name, e.g. `[providers.ow-work]`.
+    pub providers: HashMap<String, ProviderConfig>,
+    #[serde(default)]
+    pub http: HttpSettings,
+    #[serde(default)]
+    pub geocoder: GeocoderConfig,
+    /// Open-Meteo air quality endpoint used by `weather air`.
+    #[serde(default)]
+    pub air_quality: AirQualityConfig,
+    /// Limits by instance name, e.g. `[quotas.ow-work]`.
+    #[serde(default)]
+    pub quotas: HashMap<String, QuotaLimits>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct GeocoderConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct AirQualityConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
 }
 
 impl Config {
     pub fn load() -> Self {
-        fs::read_to_string(CONFIG_FILE_NAME)
+        let config = fs::read_to_string(CONFIG_FILE_NAME)
             .map(|contents| toml::from_str::<Self>(&contents).unwrap_or_default())
-            .unwrap_or_default()
+            .unwrap_or_default();
+        for provider in config.providers.values() {
+            for api_key in provider.api_keys() {
+                redact::register_secret(&api_key.key);
+            }
+        }
+        config
     }
+
+    /// Instance called `name`. A type name such as `open-weather` resolves to the
+    /// instance named after that type, or to the only instance of it.
+    pub fn resolve(&self, name: &str) -> Option<(&str, &ProviderConfig)> {
+        if let Some((name, config)) = self.providers.get_key_value(name) {
+            return Some((name, config));
+        }
+        let kind = ProviderKind::parse(name)?;
+        if let Some((name, config)) = self.providers.get_key_value(&kind.to_string()) {
+            return Some((name, config));
+        }
+        let mut of_kind = self
+            .providers
+            .iter()
+            .filter(|(_, config)| config.kind() == kind);
+        match (of_kind.next(), of_kind.next()) {
+            (Some((name, config)), None) => Some((name, config)),
+            _ => None,
+        }
+    }
+
+    /// Configured limits for instance `name`, the free tier limits of `kind` otherwise.
+    pub fn quota_limits(&self, name: &str, kind: ProviderKind) -> QuotaLimits {
+        self.quotas
+            .get(name)
+            .copied()
+            .unwrap_or_else(|| QuotaLimits::default_for(kind))
+    }
+
     fn dump(&self) {
         fs::write(CONFIG_FILE_NAME, toml::to_string(self).unwrap_or_default()).unwrap();
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum ProviderConfig {
     #[serde(rename = "weatherapi")]
-    WeatherApi { api_key: String },
+    WeatherApi {
+        api_key: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        api_keys: Vec<ApiKey>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_url: Option<String>,
+    },
     #[serde(rename = "openweather")]
-    OpenWeather { api_key: String },
+    OpenWeather {
+        api_key: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        api_keys: Vec<ApiKey>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_url: Option<String>,
+    },
+    #[serde(rename = "visualcrossing")]
+    VisualCrossing {
+        api_key: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        api_keys: Vec<ApiKey>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_url: Option<String>,
+    },
+    #[serde(rename = "fixture")]
+    Fixture {
+        /// JSON or TOML file of canned days, synthetic data when unset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<PathBuf>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seed: Option<u64>,
+    },
+    #[serde(rename = "plugin")]
+    Plugin {
+        /// Executable speaking the plugin protocol on stdin/stdout.
+        command: PathBuf,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        /// Passed to the plugin as-is with every request.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        options: BTreeMap<String, serde_json::Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        forecast_days: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        history_days: Option<u32>,
+    },
+    #[serde(rename = "metno")]
+    MetNorway {
+        /// Identifies you to MET Norway, their terms require contact details in it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        user_agent: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_url: Option<String>,
+    },
+    #[serde(rename = "nws")]
+    Nws {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_url: Option<String>,
+    },
+    /// REST source described by a url template and JSON pointers.
+    #[serde(rename = "jsonapi")]
+    JsonApi(Box<JsonApiSpec>),
+}
+
+/// Pooled key of a provider, tried after `api_key` in listed order.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct ApiKey {
+    pub label: String,
+    pub key: String,
+}
+
+/// Never prints the key itself.
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKey")
+            .field("label", &self.label)
+            .field("key", &redact::REDACTED)
+            .finish()
+    }
+}
+
+impl ProviderConfig {
+    pub fn kind(&self) -> ProviderKind {
+        match self {
+            ProviderConfig::WeatherApi { .. } => ProviderKind::WeatherApi,
+            ProviderConfig::OpenWeather { .. } => ProviderKind::OpenWeather,
+            ProviderConfig::Fixture { .. } => ProviderKind::Fixture,
+            ProviderConfig::Plugin { .. } => ProviderKind::Plugin,
+            ProviderConfig::JsonApi(_) => ProviderKind::JsonApi,
+            ProviderConfig::MetNorway { .. } => ProviderKind::MetNorway,
+            ProviderConfig::Nws { .. } => ProviderKind::Nws,
+            ProviderConfig::VisualCrossing { .. } => ProviderKind::VisualCrossing,
+        }
+    }
+
+    /// Label of the single `api_key` in front of the pooled ones.
+    pub const DEFAULT_KEY_LABEL: &str = "default";
+
+    /// Every key in rotation order, empty for providers that need none.
+    pub fn api_keys(&self) -> Vec<ApiKey> {
+        match self {
+            ProviderConfig::WeatherApi {
+                api_key, api_keys, ..
+            }
+            | ProviderConfig::OpenWeather {
+                api_key, api_keys, ..
+            }
+            | ProviderConfig::VisualCrossing {
+                api_key, api_keys, ..
+            } => {
+                let mut keys = vec![ApiKey {
+                    label: Self::DEFAULT_KEY_LABEL.to_string(),
+                    key: api_key.clone(),
+                }];
+                keys.extend(api_keys.iter().cloned());
+                keys
+            }
+            ProviderConfig::JsonApi(spec) => {
+                let mut keys: Vec<ApiKey> = spec
+                    .api_key
+                    .iter()
+                    .map(|key| ApiKey {
+                        label: Self::DEFAULT_KEY_LABEL.to_string(),
+                        key: key.clone(),
+                    })
+                    .collect();
+                keys.extend(spec.api_keys.iter().cloned());
+                keys
+            }
+            ProviderConfig::Fixture { .. }
+            | ProviderConfig::Plugin { .. }
+            | ProviderConfig::MetNorway { .. }
+            | ProviderConfig::Nws { .. } => Vec::new(),
+        }
+    }
 }
 
-pub fn set_config_for_provider(provider_name: &ProviderName) {
+/// Never prints the api key.
+impl fmt::Debug for ProviderConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, api_keys, base_url) = match self {
+            ProviderConfig::WeatherApi {
+                api_keys, base_url, ..
+            } => ("WeatherApi", api_keys, base_url),
+            ProviderConfig::OpenWeather {
+                api_keys, base_url, ..
+            } => ("OpenWeather", api_keys, base_url),
+            ProviderConfig::VisualCrossing {
+                api_keys, base_url, ..
+            } => ("VisualCrossing", api_keys, base_url),
+            ProviderConfig::Fixture { path, seed } => {
+                return f
+                    .debug_struct("Fixture")
+                    .field("path", path)
+                    .field("seed", seed)
+                    .finish();
+            }
+            // option values may carry credentials of the wrapped feed
+            ProviderConfig::Plugin {
+                command,
+                args,
+                options,
+                ..
+            } => {
+                return f
+                    .debug_struct("Plugin")
+                    .field("command", command)
+                    .field("args", args)
+                    .field("options", &options.keys().collect::<Vec<_>>())
+                    .finish();
+            }
+            ProviderConfig::JsonApi(spec) => return spec.fmt(f),
+            ProviderConfig::MetNorway {
+                user_agent,
+                base_url,
+            } => {
+                return f
+                    .debug_struct("MetNorway")
+                    .field("user_agent", user_agent)
+                    .field("base_url", base_url)
+                    .finish();
+            }
+            ProviderConfig::Nws { base_url } => {
+                return f.debug_struct("Nws").field("base_url", base_url).finish();
+            }
+        };
+        f.debug_struct(name)
+            .field("api_key", &redact::REDACTED)
+            .field("api_keys", api_keys)
+            .field("base_url", base_url)
+            .finish()
+    }
+}
+
+/// Prompts for the settings of instance `name` and returns its resolved name.
+///
+/// An existing instance keeps its type unless `kind` names another one, a new
+/// instance takes `kind` or the type `name` stands for.
+pub fn set_config_for_provider(
+    name: &str,
+    kind: Option<ProviderKind>,
+) -> Result<String, ConfigError> {
     let mut config = Config::load();
-    match provider_name {
-        ProviderName::OpenWeather => {
-            println!("Please provide api_key for OpenWeather");
+    let existing = config
+        .resolve(name)
+        .map(|(name, config)| (name.to_string(), config.kind()));
+    let (name, kind) = match (existing, kind) {
+        (Some((existing, existing_kind)), None) => (existing, existing_kind),
+        (Some((existing, existing_kind)), Some(kind)) if kind == existing_kind => (existing, kind),
+        (_, Some(kind)) => (name.to_string(), kind),
+        (None, None) => (
+            name.to_string(),
+            ProviderKind::parse(name).ok_or_else(|| ConfigError::MissingType(name.to_string()))?,
+        ),
+    };
+    if config
+        .providers
+        .get(&name)
+        .is_some_and(|existing| existing.kind() != kind)
+    {
+        config.providers.remove(&name);
+    }
+    match kind {
+        ProviderKind::OpenWeather => {
+            println!("Please provide api_key for {} (OpenWeather)", name);
             let api_key = read_user_string();
             config
                 .providers
-                .entry(provider_name.clone())
+                .entry(name.clone())
                 .and_modify(|cfg| {
                     if let ProviderConfig::OpenWeather {
                         api_key: existing_key,
+                        ..
                     } = cfg
                     {
                         *existing_key = api_key.clone();
                     }
                 })
-                .or_insert(ProviderConfig::OpenWeather { api_key });
+                .or_insert(ProviderConfig::OpenWeather {
+                    api_key,
+                    api_keys: Vec::new(),
+                    base_url: None,
+                });
         }
-        ProviderName::WeatherApi => {
-            println!("Please provide WeatherApi");
+        ProviderKind::WeatherApi => {
+            println!("Please provide api_key for {} (WeatherApi)", name);
             let api_key = read_user_string();
             config
                 .providers
-                .entry(provider_name.clone())
+                .entry(name.clone())
                 .and_modify(|cfg| {
                     if let ProviderConfig::WeatherApi {
                         api_key: existing_key,
+                        ..
+                    } = cfg
+                    {
+                        *existing_key = api_key.clone();
+                    }
+                })
+                .or_insert(ProviderConfig::WeatherApi {
+                    api_key,
+                    api_keys: Vec::new(),
+                    base_url: None,
+                });
+        }
+        ProviderKind::VisualCrossing => {
+            println!("Please provide api_key for {} (VisualCrossing)", name);
+            let api_key = read_user_string();
+            config
+                .providers
+                .entry(name.clone())
+                .and_modify(|cfg| {
+                    if let ProviderConfig::VisualCrossing {
+                        api_key: existing_key,
+                        ..
                     } = cfg
                     {
                         *existing_key = api_key.clone();
                     }
                 })
-                .or_insert(ProviderConfig::WeatherApi { api_key });
+                .or_insert(ProviderConfig::VisualCrossing {
+                    api_key,
+                    api_keys: Vec::new(),
+                    base_url: None,
+                });
+        }
+        ProviderKind::Fixture => {
+            println!("Please provide path to a JSON/TOML fixture file (empty for synthetic data)");
+            let path = read_user_string();
+            let path = (!path.is_empty()).then(|| PathBuf::from(path));
+            config
+                .providers
+                .entry(name.clone())
+                .and_modify(|cfg| {
+                    if let ProviderConfig::Fixture {
+                        path: existing_path,
+                        ..
+                    } = cfg
+                    {
+                        *existing_path = path.clone();
+                    }
+                })
+                .or_insert(ProviderConfig::Fixture { path, seed: None });
+        }
+        ProviderKind::Plugin => {
+            println!("Please provide the plugin command path");
+            let command = PathBuf::from(read_user_string());
+            config
+                .providers
+                .entry(name.clone())
+                .and_modify(|cfg| {
+                    if let ProviderConfig::Plugin {
+                        command: existing_command,
+                        ..
+                    } = cfg
+                    {
+                        *existing_command = command.clone();
+                    }
+                })
+                .or_insert(ProviderConfig::Plugin {
+                    command,
+                    args: Vec::new(),
+                    options: BTreeMap::new(),
+                    timeout_secs: None,
+                    forecast_days: None,
+                    history_days: None,
+                });
+        }
+        ProviderKind::MetNorway => {
+            println!(
+                "Please provide a User-Agent with your contact, e.g. \"my-app/1.0 you@example.com\" (empty for the default)"
+            );
+            let user_agent = read_user_string();
+            let user_agent = (!user_agent.is_empty()).then_some(user_agent);
+            config
+                .providers
+                .entry(name.clone())
+                .and_modify(|cfg| {
+                    if let ProviderConfig::MetNorway {
+                        user_agent: existing_agent,
+                        ..
+                    } = cfg
+                    {
+                        *existing_agent = user_agent.clone();
+                    }
+                })
+                .or_insert(ProviderConfig::MetNorway {
+                    user_agent,
+                    base_url: None,
+                });
+        }
+        // nothing to ask, the API needs no key
+        ProviderKind::Nws => {
+            config
+                .providers
+                .entry(name.clone())
+                .or_insert(ProviderConfig::Nws { base_url: None });
+        }
+        ProviderKind::JsonApi => {
+            println!(
+                "Please provide the url template, e.g. https://host/daily?lat={{lat}}&lon={{lon}}&date={{date}}"
+            );
+            let url = read_user_string();
+            config
+                .providers
+                .entry(name.clone())
+                .and_modify(|cfg| {
+                    if let ProviderConfig::JsonApi(spec) = cfg {
+                        spec.url = url.clone();
+                    }
+                })
+                .or_insert_with(|| {
+                    println!(
+                        "Map the answer's fields under [providers.{}.fields] in the config",
+                        name
+                    );
+                    ProviderConfig::JsonApi(Box::new(JsonApiSpec::new(url, JsonFields::default())))
+                });
         }
     }
     if config.default_provider.is_none() {
-        config.default_provider = Some(provider_name.clone());
+        config.default_provider = Some(name.clone());
     }
     config.dump();
+    Ok(name)
 }
 
-pub fn select_default_provider(provider_name: &ProviderName) {
+/// Selects instance `name` and returns its resolved name.
+pub fn select_default_provider(name: &str) -> Result<String, ConfigError> {
     let mut config = Config::load();
-    config.default_provider = Some(provider_name.clone());
+    let selected = match config.resolve(name) {
+        Some((name, _)) => name.to_string(),
+        // the fixture provider works without being configured
+        None if ProviderKind::parse(name) == Some(ProviderKind::Fixture) => name.to_string(),
+        None => return Err(ConfigError::UnknownProvider(name.to_string())),
+    };
+    config.default_provider = Some(selected.clone());
     config.dump();
+    Ok(selected)
 }
@@ -1,13 +1,30 @@
-use reqwest::blocking::get;
 use serde::Deserialize;
 use std::fmt;
 use thiserror::Error;
-pub struct Geocoder;
+
+use crate::http::{HttpClient, HttpError};
+use crate::redact::redact;
+
+pub const DEFAULT_BASE_URL: &str = "https://geocoding-api.open-meteo.com/v1";
+
+pub struct Geocoder {
+    base_url: String,
+    client: HttpClient,
+}
 
 impl Geocoder {
     // const POPULATION_THRESHOLD: u32 = 100_000;
 
+    pub fn new(base_url: Option<String>, client: HttpClient) -> Self {
+        let base_url = base_url.unwrap_or(DEFAULT_BASE_URL.to_string());
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
     pub fn resolve_address(
+        &self,
         toponym: String,
         country_code: Option<String>,
     ) -> Result<GeocoderResult, GeocoderError> {
@@ -15,9 +32,10 @@ impl Geocoder {
             .map(|c| format!("&countryCode={c}"))
             .unwrap_or_default();
         let url = format!(
-            "https://geocoding-api.open-meteo.com/v1/search?name={toponym}&count=20&language=en&format=json{country_code}"
+            "{}/search?name={toponym}&count=20&language=en&format=json{country_code}",
+            self.base_url
         );
-        let response = get(&url)?;
+        let response = self.client.get(&url)?;
         let status = response.status();
         match status {
             reqwest::StatusCode::OK => {
@@ -41,8 +59,8 @@ impl Geocoder {
 
 #[derive(Error, Debug)]
 pub enum GeocoderError {
-    #[error("failed to send request to geocoder, e={0}")]
-    FailedRequest(#[from] reqwest::Error),
+    #[error("failed to send request to geocoder, e={}", redact(&.0.to_string()))]
+    FailedRequest(#[from] HttpError),
     #[error("geocoder returned: {0}. with status code {1}")]
     GeocoderInternalError(String, u16),
     #[error("failed to parse response body")]
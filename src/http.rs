@@ -0,0 +1,316 @@
+use clap::Args;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{ACCEPT, HeaderMap, HeaderValue, RETRY_AFTER};
+use reqwest::{Certificate, Proxy};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread::sleep;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::cassette::{Cassette, CassetteError};
+use crate::quota::{QuotaError, QuotaMeter};
+
+const DEFAULT_USER_AGENT: &str = concat!("weather-cli/", env!("CARGO_PKG_VERSION"));
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_READ_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_RETRIES: u32 = 2;
+const DEFAULT_BACKOFF_MS: u64 = 500;
+/// Longest wait between attempts, a larger `Retry-After` ends retrying.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Error, Debug)]
+pub enum HttpError {
+    #[error("failed to read CA bundle {0}: {1}")]
+    CaBundle(PathBuf, String),
+    #[error("invalid proxy {0}: {1}")]
+    Proxy(String, String),
+    #[error("failed to build http client: {0}")]
+    Build(String),
+    #[error("{0}")]
+    Request(String),
+    #[error(transparent)]
+    Cassette(#[from] CassetteError),
+    #[error(transparent)]
+    Quota(#[from] QuotaError),
+}
+
+/// HTTP settings from the `[http]` config table, each overridable by a flag.
+#[derive(Args, Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct HttpSettings {
+    /// Seconds to wait for a connection
+    #[arg(long = "connect-timeout", value_name = "SECS", global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+
+    /// Seconds to wait for a response to be read
+    #[arg(long = "read-timeout", value_name = "SECS", global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_timeout_secs: Option<u64>,
+
+    /// Retries on 429 and 5xx responses
+    #[arg(long, value_name = "N", global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+
+    /// First backoff delay in milliseconds, doubled on each retry
+    #[arg(long = "backoff", value_name = "MS", global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backoff_ms: Option<u64>,
+
+    /// Proxy URL (http://, https://, socks5://)
+    #[arg(long, value_name = "URL", global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// PEM bundle with extra trusted CA certificates
+    #[arg(long, value_name = "PATH", global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+
+    /// User-Agent header sent with every request
+    #[arg(long, value_name = "UA", global = true)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+
+    /// Record every HTTP exchange into DIR/cassette.json
+    #[arg(long, value_name = "DIR", env = "WEATHER_RECORD", global = true)]
+    #[serde(skip)]
+    pub record: Option<PathBuf>,
+
+    /// Serve HTTP exchanges from DIR/cassette.json instead of the network
+    #[arg(long, value_name = "DIR", env = "WEATHER_REPLAY", global = true)]
+    #[serde(skip)]
+    pub replay: Option<PathBuf>,
+}
+
+impl HttpSettings {
+    /// Returns these settings with every value set in `overrides` replaced.
+    pub fn merge(self, overrides: HttpSettings) -> Self {
+        Self {
+            connect_timeout_secs: overrides.connect_timeout_secs.or(self.connect_timeout_secs),
+            read_timeout_secs: overrides.read_timeout_secs.or(self.read_timeout_secs),
+            retries: overrides.retries.or(self.retries),
+            backoff_ms: overrides.backoff_ms.or(self.backoff_ms),
+            proxy: overrides.proxy.or(self.proxy),
+            ca_bundle: overrides.ca_bundle.or(self.ca_bundle),
+            user_agent: overrides.user_agent.or(self.user_agent),
+            record: overrides.record.or(self.record),
+            replay: overrides.replay.or(self.replay),
+        }
+    }
+}
+
+/// Blocking client shared by the geocoder and every provider.
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    client: Client,
+    retries: u32,
+    backoff: Duration,
+    cassette: Option<Arc<Cassette>>,
+    meter: Option<Arc<QuotaMeter>>,
+}
+
+impl HttpClient {
+    pub fn new(settings: &HttpSettings) -> Result<Self, HttpError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        // the blocking client's timeout also bounds reading the response body
+        let mut builder = Client::builder()
+            .default_headers(headers)
+            .user_agent(settings.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+            .connect_timeout(Duration::from_secs(
+                settings
+                    .connect_timeout_secs
+                    .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS),
+            ))
+            .timeout(Duration::from_secs(
+                settings
+                    .read_timeout_secs
+                    .unwrap_or(DEFAULT_READ_TIMEOUT_SECS),
+            ));
+        if let Some(proxy) = &settings.proxy {
+            let proxy =
+                Proxy::all(proxy).map_err(|e| HttpError::Proxy(proxy.clone(), e.to_string()))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(path) = &settings.ca_bundle {
+            let pem =
+                fs::read(path).map_err(|e| HttpError::CaBundle(path.clone(), e.to_string()))?;
+            let certs = Certificate::from_pem_bundle(&pem)
+                .map_err(|e| HttpError::CaBundle(path.clone(), e.to_string()))?;
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        // replaying wins over recording, nothing reaches the network then
+        let cassette = match (&settings.replay, &settings.record) {
+            (Some(dir), _) => Some(Arc::new(Cassette::replay(dir)?)),
+            (None, Some(dir)) => Some(Arc::new(Cassette::record(dir)?)),
+            (None, None) => None,
+        };
+        Ok(Self {
+            client: builder
+                .build()
+                .map_err(|e| HttpError::Build(e.to_string()))?,
+            retries: settings.retries.unwrap_or(DEFAULT_RETRIES),
+            backoff: Duration::from_millis(settings.backoff_ms.unwrap_or(DEFAULT_BACKOFF_MS)),
+            cassette,
+            meter: None,
+        })
+    }
+
+    /// Same client, booking every request it sends against `meter`.
+    pub fn with_meter(&self, meter: Arc<QuotaMeter>) -> Self {
+        Self {
+            meter: Some(meter),
+            ..self.clone()
+        }
+    }
+
+    /// Sends a GET, retrying 429 and 5xx answers with exponential backoff.
+    pub fn get(&self, url: &str) -> Result<Response, HttpError> {
+        self.get_with_headers(url, HeaderMap::new())
+    }
+
+    /// Same as `get`, adding `headers` to the client's defaults.
+    pub fn get_with_headers(&self, url: &str, headers: HeaderMap) -> Result<Response, HttpError> {
+        let mut attempt = 0;
+        loop {
+            let response = self.send(url, &headers)?;
+            let status = response.status();
+            if attempt >= self.retries
+                || !(status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS)
+            {
+                return Ok(response);
+            }
+            match self.retry_delay(attempt, retry_after(response.headers())) {
+                Some(_) if self.is_replaying() => {}
+                Some(delay) => sleep(delay),
+                None => return Ok(response),
+            }
+            attempt += 1;
+        }
+    }
+
+    fn send(&self, url: &str, headers: &HeaderMap) -> Result<Response, HttpError> {
+        if let Some(cassette) = self.cassette.as_deref().filter(|_| self.is_replaying()) {
+            return Ok(cassette.next("GET", url)?.into_response());
+        }
+        if let Some(meter) = &self.meter {
+            meter.acquire()?;
+        }
+        let response = self
+            .client
+            .get(url)
+            .headers(headers.clone())
+            .send()
+            .map_err(|e| HttpError::Request(e.without_url().to_string()))?;
+        match self.cassette.as_deref() {
+            Some(cassette) => Ok(cassette.save("GET", url, response)?),
+            None => Ok(response),
+        }
+    }
+
+    fn is_replaying(&self) -> bool {
+        matches!(self.cassette.as_deref(), Some(Cassette::Replay(_)))
+    }
+
+    /// Delay before retry `attempt`, `None` when the server asks to wait too long.
+    fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        let delay = retry_after
+            .unwrap_or_else(|| self.backoff.saturating_mul(2u32.saturating_pow(attempt)));
+        match retry_after {
+            Some(delay) if delay > MAX_RETRY_DELAY => None,
+            _ => Some(delay.min(MAX_RETRY_DELAY)),
+        }
+    }
+}
+
+/// Reads `Retry-After` given in seconds, HTTP-date values are ignored.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let secs = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(backoff_ms: u64) -> HttpClient {
+        HttpClient::new(&HttpSettings {
+            backoff_ms: Some(backoff_ms),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        let client = client(100);
+        assert_eq!(
+            client.retry_delay(0, None),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            client.retry_delay(1, None),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            client.retry_delay(3, None),
+            Some(Duration::from_millis(800))
+        );
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        let client = client(10_000);
+        assert_eq!(client.retry_delay(5, None), Some(MAX_RETRY_DELAY));
+    }
+
+    #[test]
+    fn test_retry_delay_honours_retry_after() {
+        let client = client(100);
+        assert_eq!(
+            client.retry_delay(0, Some(Duration::from_secs(3))),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(client.retry_delay(0, Some(Duration::from_secs(3600))), None);
+    }
+
+    #[test]
+    fn test_merge_prefers_overrides() {
+        let config = HttpSettings {
+            retries: Some(5),
+            proxy: Some("http://config:3128".to_string()),
+            ..Default::default()
+        };
+        let flags = HttpSettings {
+            proxy: Some("socks5://flag:1080".to_string()),
+            ..Default::default()
+        };
+        let merged = config.merge(flags);
+        assert_eq!(merged.retries, Some(5));
+        assert_eq!(merged.proxy.as_deref(), Some("socks5://flag:1080"));
+    }
+
+    #[test]
+    fn test_invalid_ca_bundle_path() {
+        let settings = HttpSettings {
+            ca_bundle: Some(PathBuf::from("/nonexistent/ca.pem")),
+            ..Default::default()
+        };
+        assert!(matches!(
+            HttpClient::new(&settings),
+            Err(HttpError::CaBundle(..))
+        ));
+    }
+}
@@ -0,0 +1,284 @@
+use chrono::{DateTime, Duration, Utc};
+use reqwest::header::{
+    CACHE_CONTROL, EXPIRES, HeaderMap, HeaderValue, IF_MODIFIED_SINCE, LAST_MODIFIED,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::http::{HttpClient, HttpError};
+
+const CACHE_FILE_NAME: &str = "/tmp/weather_cli_http_cache.toml";
+/// Stale entries are kept this long for revalidation, then dropped.
+const KEEP_STALE_DAYS: i64 = 7;
+
+/// Body of an earlier answer with what is needed to reuse or revalidate it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CachedResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
+    pub body: String,
+}
+
+/// Answer of `ResponseCache::get`, a cache hit reads as a bare `200 OK`.
+#[derive(Debug)]
+pub struct Fetched {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl Fetched {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct CacheFile {
+    #[serde(default)]
+    responses: BTreeMap<String, CachedResponse>,
+}
+
+/// On-disk cache of GET answers by URL, honouring `Expires`/`Cache-Control: max-age`
+/// and revalidating stale entries with `If-Modified-Since`.
+///
+/// Meant for keyless endpoints only, URLs are stored as-is.
+pub struct ResponseCache {
+    path: PathBuf,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::with_path(PathBuf::from(CACHE_FILE_NAME))
+    }
+}
+
+impl ResponseCache {
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn load(&self) -> CacheFile {
+        fs::read_to_string(&self.path)
+            .map(|contents| toml::from_str::<CacheFile>(&contents).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    fn dump(&self, file: &CacheFile) {
+        // caching must never break a weather request
+        let _ = fs::write(&self.path, toml::to_string(file).unwrap_or_default());
+    }
+
+    /// GET `url` through the cache: a fresh entry is served without a request, a stale
+    /// one is revalidated, a new `2xx` answer is stored. Errors are passed back as-is.
+    pub fn get(
+        &self,
+        client: &HttpClient,
+        url: &str,
+        mut headers: HeaderMap,
+    ) -> Result<Fetched, HttpError> {
+        let now = Utc::now();
+        if let Some(body) = self.fresh(url, now) {
+            return Ok(Fetched {
+                status: 200,
+                headers: HeaderMap::new(),
+                body,
+            });
+        }
+        headers.extend(self.validators(url));
+        let response = client.get_with_headers(url, headers)?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response
+            .text()
+            .map_err(|e| HttpError::Request(e.without_url().to_string()))?;
+        let body = match status {
+            304 => match self.revalidated(url, &headers, now) {
+                Some(body) => {
+                    return Ok(Fetched {
+                        status: 200,
+                        headers,
+                        body,
+                    });
+                }
+                None => body,
+            },
+            200..=299 => {
+                self.store(url, &headers, &body, now);
+                body
+            }
+            _ => body,
+        };
+        Ok(Fetched {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    /// Keeps the entry of `url` at least until `until`, for answers that outlive their headers.
+    pub fn keep_until(&self, url: &str, until: DateTime<Utc>) {
+        let mut file = self.load();
+        if let Some(cached) = file.responses.get_mut(url) {
+            cached.expires = cached.expires.max(Some(until));
+            self.dump(&file);
+        }
+    }
+
+    /// Body cached for `url` that has not expired as of `now`.
+    pub fn fresh(&self, url: &str, now: DateTime<Utc>) -> Option<String> {
+        self.load()
+            .responses
+            .remove(url)
+            .filter(|cached| cached.expires.is_some_and(|expires| expires > now))
+            .map(|cached| cached.body)
+    }
+
+    /// `If-Modified-Since` for a stale entry of `url`, nothing without one.
+    pub fn validators(&self, url: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let last_modified = self
+            .load()
+            .responses
+            .remove(url)
+            .and_then(|cached| cached.last_modified)
+            .and_then(|value| HeaderValue::from_str(&value).ok());
+        if let Some(value) = last_modified {
+            headers.insert(IF_MODIFIED_SINCE, value);
+        }
+        headers
+    }
+
+    /// Cached body of `url` after a `304 Not Modified`, with its expiry taken from `headers`.
+    pub fn revalidated(
+        &self,
+        url: &str,
+        headers: &HeaderMap,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let mut file = self.load();
+        let cached = file.responses.get_mut(url)?;
+        cached.expires = expires(headers, now);
+        let body = cached.body.clone();
+        self.dump(&file);
+        Some(body)
+    }
+
+    /// Remembers a `200 OK` answer of `url`.
+    pub fn store(&self, url: &str, headers: &HeaderMap, body: &str, now: DateTime<Utc>) {
+        let mut file = self.load();
+        file.responses.retain(|_, cached| {
+            cached
+                .expires
+                .is_none_or(|expires| expires + Duration::days(KEEP_STALE_DAYS) > now)
+        });
+        file.responses.insert(
+            url.to_string(),
+            CachedResponse {
+                last_modified: header(headers, LAST_MODIFIED.as_str()),
+                expires: expires(headers, now),
+                body: body.to_string(),
+            },
+        );
+        self.dump(&file);
+    }
+}
+
+fn header(headers: &HeaderMap, name: &str) -> Option<String> {
+    Some(headers.get(name)?.to_str().ok()?.to_string())
+}
+
+/// Expiry from `Cache-Control: max-age`, which wins, or an HTTP-date `Expires`.
+fn expires(headers: &HeaderMap, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let max_age = header(headers, CACHE_CONTROL.as_str()).and_then(|value| {
+        value
+            .split(',')
+            .find_map(|directive| directive.trim().strip_prefix("max-age="))
+            .and_then(|secs| secs.parse::<i64>().ok())
+    });
+    if let Some(secs) = max_age {
+        return Some(now + Duration::seconds(secs));
+    }
+    let expires = header(headers, EXPIRES.as_str())?;
+    DateTime::parse_from_rfc2822(&expires)
+        .ok()
+        .map(|expires| expires.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(name: &str) -> ResponseCache {
+        ResponseCache::with_path(std::env::temp_dir().join(format!(
+            "weather-http-cache-{}-{}.toml",
+            name,
+            std::process::id()
+        )))
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-07-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_fresh_until_expires() {
+        let cache = cache("fresh");
+        let answer = headers(&[
+            ("expires", "Tue, 01 Jul 2025 12:30:00 GMT"),
+            ("last-modified", "Tue, 01 Jul 2025 11:50:00 GMT"),
+        ]);
+        cache.store("https://example.test/a", &answer, "{}", now());
+
+        assert_eq!(
+            cache.fresh("https://example.test/a", now()).as_deref(),
+            Some("{}")
+        );
+        assert!(
+            cache
+                .fresh("https://example.test/a", now() + Duration::hours(1))
+                .is_none()
+        );
+        assert_eq!(
+            cache.validators("https://example.test/a")[IF_MODIFIED_SINCE],
+            "Tue, 01 Jul 2025 11:50:00 GMT"
+        );
+        let _ = fs::remove_file(&cache.path);
+    }
+
+    #[test]
+    fn test_revalidated_extends_expiry() {
+        let cache = cache("revalidate");
+        cache.store("https://example.test/b", &HeaderMap::new(), "body", now());
+        assert!(cache.fresh("https://example.test/b", now()).is_none());
+
+        let not_modified = headers(&[("cache-control", "public, max-age=600")]);
+        assert_eq!(
+            cache
+                .revalidated("https://example.test/b", &not_modified, now())
+                .as_deref(),
+            Some("body")
+        );
+        assert!(cache.fresh("https://example.test/b", now()).is_some());
+        assert!(
+            cache
+                .revalidated("https://example.test/c", &not_modified, now())
+                .is_none()
+        );
+        let _ = fs::remove_file(&cache.path);
+    }
+}
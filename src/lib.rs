@@ -1,7 +1,13 @@
 pub mod argparser;
+pub mod astronomy;
+pub mod cassette;
 pub mod cli;
 pub mod configs;
 pub mod geocoder;
+pub mod http;
+pub mod http_cache;
 pub mod input;
 pub mod provider_builder;
 pub mod providers;
+pub mod quota;
+pub mod redact;
@@ -1,12 +1,25 @@
 use clap::Parser;
 use weather::argparser::{Argparser, Commands};
-use weather::cli::run;
+use weather::cli::{
+    IndicesOptions, check_alerts, list_providers, run, run_range, show_air, show_alerts,
+    show_indices, show_quota, show_sun,
+};
 use weather::configs;
 
 fn main() {
     let args = Argparser::parse();
-    if args.command.is_none() {
-        let toponym_present = args.toponym.is_some();
+    let toponym = match &args.command {
+        None => Some(&args.toponym),
+        Some(
+            Commands::Alerts { toponym }
+            | Commands::Air { toponym, .. }
+            | Commands::Sun { toponym, .. }
+            | Commands::Indices { toponym, .. },
+        ) => Some(toponym),
+        Some(_) => None,
+    };
+    if let Some(toponym) = toponym {
+        let toponym_present = toponym.is_some();
         let lat_long_present = args.latitude.is_some() && args.longitude.is_some();
 
         if !toponym_present && !lat_long_present {
@@ -18,23 +31,115 @@ fn main() {
     }
     match args.command {
         None => {
-            if let Err(e) = run(
+            let result = match args.until {
+                Some(until) => run_range(
+                    configs::Config::load(),
+                    args.latitude,
+                    args.longitude,
+                    args.toponym,
+                    args.country_code,
+                    args.date..=until,
+                    args.http,
+                ),
+                None => run(
+                    configs::Config::load(),
+                    args.latitude,
+                    args.longitude,
+                    args.toponym,
+                    args.country_code,
+                    args.date,
+                    args.http,
+                ),
+            };
+            let result = result.and_then(|alerts| check_alerts(&alerts, args.fail_on_alert));
+            if let Err(e) = result {
+                eprintln!("{}", e);
+                std::process::exit(e.exit_code());
+            }
+        }
+        Some(Commands::Alerts { toponym }) => {
+            let result = show_alerts(
+                configs::Config::load(),
                 args.latitude,
                 args.longitude,
-                args.toponym,
+                toponym,
                 args.country_code,
-                args.date,
+                args.http,
+            )
+            .and_then(|alerts| check_alerts(&alerts, args.fail_on_alert));
+            if let Err(e) = result {
+                eprintln!("{}", e);
+                std::process::exit(e.exit_code());
+            }
+        }
+        Some(Commands::Air { toponym, source }) => {
+            if let Err(e) = show_air(
+                configs::Config::load(),
+                args.latitude,
+                args.longitude,
+                toponym,
+                args.country_code,
+                source,
+                args.http,
             ) {
                 eprintln!("{}", e);
+                std::process::exit(e.exit_code());
             }
         }
-        Some(Commands::Configure { provider_name }) => {
-            configs::set_config_for_provider(&provider_name);
-            println!("updated config for {}", provider_name);
+        Some(Commands::Sun { toponym, date }) => {
+            if let Err(e) = show_sun(
+                configs::Config::load(),
+                args.latitude,
+                args.longitude,
+                toponym,
+                args.country_code,
+                date,
+                args.http,
+            ) {
+                eprintln!("{}", e);
+                std::process::exit(e.exit_code());
+            }
+        }
+        Some(Commands::Indices {
+            toponym,
+            date,
+            indices,
+            json,
+        }) => {
+            if let Err(e) = show_indices(
+                configs::Config::load(),
+                args.latitude,
+                args.longitude,
+                toponym,
+                args.country_code,
+                IndicesOptions {
+                    date,
+                    kinds: indices,
+                    json,
+                },
+                args.http,
+            ) {
+                eprintln!("{}", e);
+                std::process::exit(e.exit_code());
+            }
         }
-        Some(Commands::Select { provider_name }) => {
-            configs::select_default_provider(&provider_name);
-            println!("selected {}", provider_name);
+        Some(Commands::Configure { name, kind }) => {
+            match configs::set_config_for_provider(&name, kind) {
+                Ok(name) => println!("updated config for {}", name),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }
+            }
         }
+        Some(Commands::Select { name }) => match configs::select_default_provider(&name) {
+            Ok(name) => println!("selected {}", name),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        },
+        Some(Commands::Providers) => list_providers(),
+        Some(Commands::Quota) => show_quota(),
     }
 }
@@ -1,14 +1,24 @@
+use std::sync::Arc;
 use thiserror::Error;
 
+use crate::argparser::ProviderKind;
 use crate::configs::{Config, ProviderConfig};
-use crate::providers::WeatherProvider;
-use crate::providers::{open_weather::OpenWeather, weather_api::WeatherApi};
+use crate::http::HttpClient;
+use crate::providers::json_api::JsonApi;
+use crate::providers::met_norway::MetNorway;
+use crate::providers::nws::Nws;
+use crate::providers::plugin::Plugin;
+use crate::providers::rotating::{KeyedProvider, RotatingProvider};
+use crate::providers::visual_crossing::VisualCrossing;
+use crate::providers::{ProviderCapabilities, WeatherProvider};
+use crate::providers::{fixture::Fixture, open_weather::OpenWeather, weather_api::WeatherApi};
+use crate::quota::QuotaMeter;
 #[derive(Error, Debug)]
 pub enum ProviderBuilderError {
     #[error("no provider selected. read --help")]
     NoDefaultProvider,
-    #[error("no config for provider. read --help")]
-    ProvidersIsNotConfigured,
+    #[error("no config for provider '{0}'. read --help")]
+    ProvidersIsNotConfigured(String),
 }
 
 pub struct ProviderBuilder;
@@ -16,73 +26,185 @@ pub struct ProviderBuilder;
 impl ProviderBuilder {
     pub fn build_provider(
         config: Config,
+        client: HttpClient,
     ) -> Result<Box<dyn WeatherProvider>, ProviderBuilderError> {
-        let provider = config
+        let selected = config
             .default_provider
+            .as_deref()
             .ok_or(ProviderBuilderError::NoDefaultProvider)?;
-        let config = config
-            .providers
-            .get(&provider)
-            .ok_or(ProviderBuilderError::ProvidersIsNotConfigured)?;
+        // the fixture provider works without being configured
+        let synthetic = ProviderConfig::Fixture {
+            path: None,
+            seed: None,
+        };
+        let (name, provider_config) = config
+            .resolve(selected)
+            .or(
+                (ProviderKind::parse(selected) == Some(ProviderKind::Fixture))
+                    .then_some((selected, &synthetic)),
+            )
+            .ok_or_else(|| ProviderBuilderError::ProvidersIsNotConfigured(selected.to_string()))?;
+        let kind = provider_config.kind();
+        let limits = config.quota_limits(name, kind);
+        let api_keys = provider_config.api_keys();
+        if api_keys.is_empty() {
+            return Ok(Self::build_for_key(provider_config, String::new(), client));
+        }
+        let keys = api_keys
+            .into_iter()
+            .map(|api_key| {
+                let meter = Arc::new(QuotaMeter::new(name, &api_key.label, &api_key.key, limits));
+                KeyedProvider {
+                    provider: Self::build_for_key(
+                        provider_config,
+                        api_key.key,
+                        client.with_meter(meter.clone()),
+                    ),
+                    meter,
+                }
+            })
+            .collect();
+        Ok(Box::new(RotatingProvider::new(
+            Self::capabilities_of(provider_config),
+            keys,
+        )))
+    }
+
+    /// Provider instance using `api_key`, which keyless providers ignore.
+    fn build_for_key(
+        config: &ProviderConfig,
+        api_key: String,
+        client: HttpClient,
+    ) -> Box<dyn WeatherProvider> {
         match config {
-            ProviderConfig::OpenWeather { api_key } => {
-                Ok(Box::new(OpenWeather::new(api_key.clone())))
+            ProviderConfig::OpenWeather { base_url, .. } => {
+                Box::new(OpenWeather::new(api_key, base_url.clone(), client))
             }
-            ProviderConfig::WeatherApi { api_key } => {
-                Ok(Box::new(WeatherApi::new(api_key.clone())))
+            ProviderConfig::WeatherApi { base_url, .. } => {
+                Box::new(WeatherApi::new(api_key, base_url.clone(), client))
+            }
+            ProviderConfig::VisualCrossing { base_url, .. } => {
+                Box::new(VisualCrossing::new(api_key, base_url.clone(), client))
+            }
+            ProviderConfig::Fixture { path, seed } => Box::new(Fixture::new(path.clone(), *seed)),
+            ProviderConfig::Plugin {
+                command,
+                args,
+                options,
+                timeout_secs,
+                ..
+            } => Box::new(Plugin::new(
+                command.clone(),
+                args.clone(),
+                options.clone(),
+                *timeout_secs,
+                Self::capabilities_of(config),
+            )),
+            ProviderConfig::MetNorway {
+                user_agent,
+                base_url,
+            } => Box::new(MetNorway::new(user_agent.clone(), base_url.clone(), client)),
+            ProviderConfig::Nws { base_url } => Box::new(Nws::new(base_url.clone(), client)),
+            ProviderConfig::JsonApi(spec) => {
+                Box::new(JsonApi::new((**spec).clone(), api_key, client))
             }
         }
     }
+
+    pub fn capabilities(kind: &ProviderKind) -> ProviderCapabilities {
+        match kind {
+            ProviderKind::OpenWeather => OpenWeather::CAPABILITIES,
+            ProviderKind::WeatherApi => WeatherApi::CAPABILITIES,
+            ProviderKind::Fixture => Fixture::CAPABILITIES,
+            ProviderKind::Plugin => Plugin::CAPABILITIES,
+            ProviderKind::JsonApi => JsonApi::CAPABILITIES,
+            ProviderKind::MetNorway => MetNorway::CAPABILITIES,
+            ProviderKind::Nws => Nws::CAPABILITIES,
+            ProviderKind::VisualCrossing => VisualCrossing::CAPABILITIES,
+        }
+    }
+
+    /// Capabilities of a configured instance, plugins and json apis may narrow their date range.
+    pub fn capabilities_of(config: &ProviderConfig) -> ProviderCapabilities {
+        let defaults = Self::capabilities(&config.kind());
+        match config {
+            ProviderConfig::Plugin {
+                forecast_days,
+                history_days,
+                ..
+            } => ProviderCapabilities {
+                forecast_days: forecast_days.unwrap_or(defaults.forecast_days),
+                history_days: history_days.or(defaults.history_days),
+                ..defaults
+            },
+            ProviderConfig::JsonApi(spec) => ProviderCapabilities {
+                forecast_days: spec.forecast_days.unwrap_or(defaults.forecast_days),
+                history_days: spec.history_days.or(defaults.history_days),
+                requires_key: spec.api_key.is_some(),
+                ..defaults
+            },
+            _ => defaults,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::argparser::ProviderName;
     use crate::configs::Config;
+    use crate::http::HttpSettings;
     use std::collections::HashMap;
 
     fn make_test_config(
-        default_provider: Option<ProviderName>,
-        configured_provider: Option<(ProviderName, ProviderConfig)>,
+        default_provider: Option<&str>,
+        configured_provider: Option<(&str, ProviderConfig)>,
     ) -> Config {
         let mut providers = HashMap::new();
         if let Some((name, config)) = configured_provider {
-            providers.insert(name, config);
+            providers.insert(name.to_string(), config);
         }
         Config {
-            default_provider,
+            default_provider: default_provider.map(str::to_string),
             providers,
+            ..Default::default()
         }
     }
 
+    fn client() -> HttpClient {
+        HttpClient::new(&HttpSettings::default()).unwrap()
+    }
+
     #[test]
     fn test_build_provider_open_weather_success() {
         let config = make_test_config(
-            Some(ProviderName::OpenWeather),
+            Some("OpenWeather"),
             Some((
-                ProviderName::OpenWeather,
+                "OpenWeather",
                 ProviderConfig::OpenWeather {
                     api_key: "ow_key".to_string(),
+                    api_keys: Vec::new(),
+                    base_url: None,
                 },
             )),
         );
-        let result = ProviderBuilder::build_provider(config);
+        let result = ProviderBuilder::build_provider(config, client());
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_build_provider_weather_api_success() {
         let config = make_test_config(
-            Some(ProviderName::WeatherApi),
+            Some("WeatherApi"),
             Some((
-                ProviderName::WeatherApi,
+                "WeatherApi",
                 ProviderConfig::WeatherApi {
                     api_key: "wa_key".to_string(),
+                    api_keys: Vec::new(),
+                    base_url: None,
                 },
             )),
         );
-        let result = ProviderBuilder::build_provider(config);
+        let result = ProviderBuilder::build_provider(config, client());
         assert!(result.is_ok());
     }
 
@@ -91,13 +213,15 @@ mod tests {
         let config = make_test_config(
             None,
             Some((
-                ProviderName::WeatherApi,
+                "WeatherApi",
                 ProviderConfig::WeatherApi {
                     api_key: "wa_key".to_string(),
+                    api_keys: Vec::new(),
+                    base_url: None,
                 },
             )),
         );
-        let result = ProviderBuilder::build_provider(config);
+        let result = ProviderBuilder::build_provider(config, client());
         assert!(matches!(
             result,
             Err(ProviderBuilderError::NoDefaultProvider)
@@ -108,18 +232,95 @@ mod tests {
     fn test_build_provider_not_configured() {
         // Default is OpenWeather, but only WeatherApi is configured
         let config = make_test_config(
-            Some(ProviderName::OpenWeather),
+            Some("OpenWeather"),
             Some((
-                ProviderName::WeatherApi,
+                "WeatherApi",
                 ProviderConfig::WeatherApi {
                     api_key: "wa_key".to_string(),
+                    api_keys: Vec::new(),
+                    base_url: None,
                 },
             )),
         );
-        let result = ProviderBuilder::build_provider(config);
+        let result = ProviderBuilder::build_provider(config, client());
         assert!(matches!(
             result,
-            Err(ProviderBuilderError::ProvidersIsNotConfigured)
+            Err(ProviderBuilderError::ProvidersIsNotConfigured(_))
+        ));
+    }
+
+    #[test]
+    fn test_capabilities_match_built_provider() {
+        let config = make_test_config(
+            Some("OpenWeather"),
+            Some((
+                "OpenWeather",
+                ProviderConfig::OpenWeather {
+                    api_key: "ow_key".to_string(),
+                    api_keys: Vec::new(),
+                    base_url: None,
+                },
+            )),
+        );
+        let provider = ProviderBuilder::build_provider(config, client()).unwrap();
+        assert_eq!(
+            provider.capabilities(),
+            ProviderBuilder::capabilities(&ProviderKind::OpenWeather)
+        );
+    }
+
+    #[test]
+    fn test_build_fixture_provider_without_config() {
+        let config = make_test_config(Some("fixture"), None);
+        let provider = ProviderBuilder::build_provider(config, client()).unwrap();
+        assert!(!provider.capabilities().requires_key);
+    }
+
+    fn open_weather(api_key: &str) -> ProviderConfig {
+        ProviderConfig::OpenWeather {
+            api_key: api_key.to_string(),
+            api_keys: Vec::new(),
+            base_url: None,
+        }
+    }
+
+    #[test]
+    fn test_build_named_instance() {
+        let mut config = make_test_config(
+            Some("ow-work"),
+            Some(("ow-work", open_weather("ow_work_key"))),
+        );
+        config
+            .providers
+            .insert("ow-home".to_string(), open_weather("ow_home_key"));
+        assert!(ProviderBuilder::build_provider(config, client()).is_ok());
+    }
+
+    #[test]
+    fn test_type_name_resolves_single_instance() {
+        let config = make_test_config(
+            Some("open-weather"),
+            Some(("ow-work", open_weather("ow_work_key"))),
+        );
+        assert_eq!(
+            config.resolve("open-weather").map(|(name, _)| name),
+            Some("ow-work")
+        );
+        assert!(ProviderBuilder::build_provider(config, client()).is_ok());
+    }
+
+    #[test]
+    fn test_type_name_with_several_instances_is_ambiguous() {
+        let mut config = make_test_config(
+            Some("open-weather"),
+            Some(("ow-work", open_weather("ow_work_key"))),
+        );
+        config
+            .providers
+            .insert("ow-home".to_string(), open_weather("ow_home_key"));
+        assert!(matches!(
+            ProviderBuilder::build_provider(config, client()),
+            Err(ProviderBuilderError::ProvidersIsNotConfigured(_))
         ));
     }
 }
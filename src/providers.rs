@@ -1,10 +1,26 @@
+pub mod air_quality;
+pub mod alerts;
+mod capabilities;
+pub mod condition;
 pub mod error;
+pub mod fixture;
+pub mod indices;
+pub mod json_api;
+pub mod met_norway;
 mod models;
+pub mod nws;
+pub mod open_meteo;
 pub mod open_weather;
+pub mod plugin;
 mod provider_trait;
 mod providers_report;
+pub mod rotating;
+pub mod units;
+pub mod visual_crossing;
 pub mod weather_api;
+pub mod wind;
 
+pub use capabilities::ProviderCapabilities;
 pub use error::ProviderResult;
 pub use provider_trait::WeatherProvider;
-pub use providers_report::ProvidersReport;
+pub use providers_report::{DataWarning, PrecipitationType, ProvidersReport, WindStatistic};
@@ -0,0 +1,430 @@
+use chrono::{DateTime, Local, Utc};
+use std::fmt;
+
+use super::models::open_meteo::OpenMeteoAirCurrent;
+use super::models::openweather_api::OpenWeatherAirSample;
+use super::models::weather_api::WeatherApiCurrent;
+
+/// Pollutants both AQI scales are computed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pollutant {
+    Pm2_5,
+    Pm10,
+    O3,
+    No2,
+    So2,
+    Co,
+}
+
+impl Pollutant {
+    /// g/mol, to turn µg/m³ into ppb at 25 °C and 1 atm.
+    fn molar_mass(&self) -> Option<f32> {
+        match self {
+            Self::O3 => Some(48.00),
+            Self::No2 => Some(46.01),
+            Self::So2 => Some(64.07),
+            Self::Co => Some(28.01),
+            Self::Pm2_5 | Self::Pm10 => None,
+        }
+    }
+}
+
+impl fmt::Display for Pollutant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Pm2_5 => "PM2.5",
+            Self::Pm10 => "PM10",
+            Self::O3 => "O3",
+            Self::No2 => "NO2",
+            Self::So2 => "SO2",
+            Self::Co => "CO",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AqiScale {
+    /// US EPA, 0 to 500.
+    UsEpa,
+    /// European Environment Agency, levels 1 to 6.
+    European,
+}
+
+/// Index on one scale, set by the pollutant with the worst sub-index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aqi {
+    pub scale: AqiScale,
+    pub value: u16,
+    pub pollutant: Pollutant,
+}
+
+impl Aqi {
+    pub fn category(&self) -> &'static str {
+        match (self.scale, self.value) {
+            (AqiScale::UsEpa, 0..=50) => "Good",
+            (AqiScale::UsEpa, 51..=100) => "Moderate",
+            (AqiScale::UsEpa, 101..=150) => "Unhealthy for Sensitive Groups",
+            (AqiScale::UsEpa, 151..=200) => "Unhealthy",
+            (AqiScale::UsEpa, 201..=300) => "Very Unhealthy",
+            (AqiScale::UsEpa, _) => "Hazardous",
+            (AqiScale::European, 0..=1) => "Good",
+            (AqiScale::European, 2) => "Fair",
+            (AqiScale::European, 3) => "Moderate",
+            (AqiScale::European, 4) => "Poor",
+            (AqiScale::European, 5) => "Very poor",
+            (AqiScale::European, _) => "Extremely poor",
+        }
+    }
+}
+
+impl fmt::Display for Aqi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ({})", self.value, self.category(), self.pollutant)
+    }
+}
+
+/// `(lowest, highest)` concentration of each band, truncated units, with its `(lowest, highest)` index.
+type UsBand = (f32, f32, u16, u16);
+
+/// µg/m³, PM2.5 breakpoints as revised in 2024.
+const US_PM2_5: [UsBand; 6] = [
+    (0.0, 9.0, 0, 50),
+    (9.1, 35.4, 51, 100),
+    (35.5, 55.4, 101, 150),
+    (55.5, 125.4, 151, 200),
+    (125.5, 225.4, 201, 300),
+    (225.5, 325.4, 301, 500),
+];
+/// µg/m³.
+const US_PM10: [UsBand; 6] = [
+    (0., 54., 0, 50),
+    (55., 154., 51, 100),
+    (155., 254., 101, 150),
+    (255., 354., 151, 200),
+    (355., 424., 201, 300),
+    (425., 604., 301, 500),
+];
+/// ppb, the 8-hour scale, which stops at 200 ppb, then the top band of the 1-hour scale;
+/// values in between are rated at the start of that band.
+const US_O3: [UsBand; 6] = [
+    (0., 54., 0, 50),
+    (55., 70., 51, 100),
+    (71., 85., 101, 150),
+    (86., 105., 151, 200),
+    (106., 200., 201, 300),
+    (405., 604., 301, 500),
+];
+/// ppb.
+const US_NO2: [UsBand; 6] = [
+    (0., 53., 0, 50),
+    (54., 100., 51, 100),
+    (101., 360., 101, 150),
+    (361., 649., 151, 200),
+    (650., 1249., 201, 300),
+    (1250., 2049., 301, 500),
+];
+/// ppb.
+const US_SO2: [UsBand; 6] = [
+    (0., 35., 0, 50),
+    (36., 75., 51, 100),
+    (76., 185., 101, 150),
+    (186., 304., 151, 200),
+    (305., 604., 201, 300),
+    (605., 1004., 301, 500),
+];
+/// ppm.
+const US_CO: [UsBand; 6] = [
+    (0.0, 4.4, 0, 50),
+    (4.5, 9.4, 51, 100),
+    (9.5, 12.4, 101, 150),
+    (12.5, 15.4, 151, 200),
+    (15.5, 30.4, 201, 300),
+    (30.5, 50.4, 301, 500),
+];
+
+/// Upper bound of levels 1 to 5 in µg/m³, anything above is level 6.
+const EU_PM2_5: [f32; 5] = [5., 15., 50., 90., 140.];
+const EU_PM10: [f32; 5] = [15., 45., 120., 195., 270.];
+const EU_NO2: [f32; 5] = [10., 25., 60., 100., 150.];
+const EU_O3: [f32; 5] = [60., 100., 120., 160., 180.];
+const EU_SO2: [f32; 5] = [20., 40., 125., 190., 275.];
+
+/// Current pollutant concentrations and pollen counts at a location.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AirQuality {
+    pub source: String,
+    pub observed: Option<DateTime<Utc>>,
+    /// µg/m³.
+    pub pm2_5: Option<f32>,
+    pub pm10: Option<f32>,
+    pub o3: Option<f32>,
+    pub no2: Option<f32>,
+    pub so2: Option<f32>,
+    pub co: Option<f32>,
+    /// grains/m³ by plant, only from sources that measure pollen.
+    pub pollen: Vec<(String, f32)>,
+}
+
+impl AirQuality {
+    pub fn concentration(&self, pollutant: Pollutant) -> Option<f32> {
+        match pollutant {
+            Pollutant::Pm2_5 => self.pm2_5,
+            Pollutant::Pm10 => self.pm10,
+            Pollutant::O3 => self.o3,
+            Pollutant::No2 => self.no2,
+            Pollutant::So2 => self.so2,
+            Pollutant::Co => self.co,
+        }
+    }
+
+    /// US EPA AQI from the current concentrations, truncated as the EPA prescribes.
+    /// Sources give hourly values, not the 8- or 24-hour averages the official index uses.
+    pub fn us_aqi(&self) -> Option<Aqi> {
+        [
+            (Pollutant::Pm2_5, &US_PM2_5),
+            (Pollutant::Pm10, &US_PM10),
+            (Pollutant::O3, &US_O3),
+            (Pollutant::No2, &US_NO2),
+            (Pollutant::So2, &US_SO2),
+            (Pollutant::Co, &US_CO),
+        ]
+        .into_iter()
+        .filter_map(|(pollutant, bands)| {
+            let value = us_sub_index(
+                us_concentration(pollutant, self.concentration(pollutant)?),
+                bands,
+            );
+            Some(Aqi {
+                scale: AqiScale::UsEpa,
+                value,
+                pollutant,
+            })
+        })
+        .max_by_key(|aqi| aqi.value)
+    }
+
+    /// European AQI level, CO is not part of it.
+    pub fn eu_aqi(&self) -> Option<Aqi> {
+        [
+            (Pollutant::Pm2_5, &EU_PM2_5),
+            (Pollutant::Pm10, &EU_PM10),
+            (Pollutant::O3, &EU_O3),
+            (Pollutant::No2, &EU_NO2),
+            (Pollutant::So2, &EU_SO2),
+        ]
+        .into_iter()
+        .filter_map(|(pollutant, bounds)| {
+            let concentration = self.concentration(pollutant)?;
+            let level = bounds
+                .iter()
+                .position(|upper| concentration <= *upper)
+                .unwrap_or(bounds.len());
+            Some(Aqi {
+                scale: AqiScale::European,
+                value: level as u16 + 1,
+                pollutant,
+            })
+        })
+        .max_by_key(|aqi| aqi.value)
+    }
+}
+
+/// µg/m³ into the unit and precision of the EPA breakpoints: ppb for gases, ppm for CO.
+fn us_concentration(pollutant: Pollutant, micrograms: f32) -> f32 {
+    let truncate = |value: f32, digits: i32| {
+        let scale = 10f32.powi(digits);
+        (value * scale + 1e-3).floor() / scale
+    };
+    let ppb = |mass: f32| micrograms * 24.45 / mass;
+    match (pollutant, pollutant.molar_mass()) {
+        (Pollutant::Pm2_5, _) => truncate(micrograms, 1),
+        (Pollutant::Co, Some(mass)) => truncate(ppb(mass) / 1000., 1),
+        (_, Some(mass)) => truncate(ppb(mass), 0),
+        (_, None) => truncate(micrograms, 0),
+    }
+}
+
+/// Linear interpolation inside the band holding `concentration`, 500 beyond the scale.
+fn us_sub_index(concentration: f32, bands: &[UsBand]) -> u16 {
+    bands
+        .iter()
+        .find(|(_, c_hi, _, _)| concentration <= *c_hi)
+        .map(|&(c_lo, c_hi, i_lo, i_hi)| {
+            let c = concentration.max(c_lo);
+            let index = (i_hi - i_lo) as f32 / (c_hi - c_lo) * (c - c_lo) + i_lo as f32;
+            index.round() as u16
+        })
+        .unwrap_or(500)
+}
+
+impl fmt::Display for AirQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Air Quality ({}", self.source)?;
+        if let Some(observed) = self.observed {
+            write!(
+                f,
+                ", {}",
+                observed.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            )?;
+        }
+        write!(f, "):")?;
+        for pollutant in [
+            Pollutant::Pm2_5,
+            Pollutant::Pm10,
+            Pollutant::O3,
+            Pollutant::No2,
+            Pollutant::So2,
+            Pollutant::Co,
+        ] {
+            if let Some(value) = self.concentration(pollutant) {
+                write!(f, "\n{:<14}: {:.1} µg/m³", pollutant.to_string(), value)?;
+            }
+        }
+        match self.us_aqi() {
+            Some(aqi) => write!(f, "\nUS AQI        : {}", aqi)?,
+            None => write!(f, "\nUS AQI        : N/A")?,
+        }
+        match self.eu_aqi() {
+            Some(aqi) => write!(f, "\nEuropean AQI  : {}", aqi)?,
+            None => write!(f, "\nEuropean AQI  : N/A")?,
+        }
+        if !self.pollen.is_empty() {
+            let counts: Vec<String> = self
+                .pollen
+                .iter()
+                .map(|(plant, count)| format!("{} {:.0}", plant, count))
+                .collect();
+            write!(f, "\nPollen        : {} grains/m³", counts.join(" | "))?;
+        }
+        Ok(())
+    }
+}
+
+fn from_timestamp(secs: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(secs, 0)
+}
+
+impl From<WeatherApiCurrent> for AirQuality {
+    fn from(value: WeatherApiCurrent) -> Self {
+        let air = value.air_quality;
+        Self {
+            source: "WeatherAPI".to_string(),
+            observed: value.last_updated_epoch.and_then(from_timestamp),
+            pm2_5: air.pm2_5,
+            pm10: air.pm10,
+            o3: air.o3,
+            no2: air.no2,
+            so2: air.so2,
+            co: air.co,
+            pollen: Vec::new(),
+        }
+    }
+}
+
+impl From<OpenWeatherAirSample> for AirQuality {
+    fn from(value: OpenWeatherAirSample) -> Self {
+        let air = value.components;
+        Self {
+            source: "OpenWeather".to_string(),
+            observed: from_timestamp(value.dt),
+            pm2_5: air.pm2_5,
+            pm10: air.pm10,
+            o3: air.o3,
+            no2: air.no2,
+            so2: air.so2,
+            co: air.co,
+            pollen: Vec::new(),
+        }
+    }
+}
+
+impl From<OpenMeteoAirCurrent> for AirQuality {
+    fn from(value: OpenMeteoAirCurrent) -> Self {
+        let pollen = [
+            ("alder", value.alder_pollen),
+            ("birch", value.birch_pollen),
+            ("grass", value.grass_pollen),
+            ("mugwort", value.mugwort_pollen),
+            ("olive", value.olive_pollen),
+            ("ragweed", value.ragweed_pollen),
+        ]
+        .into_iter()
+        .filter_map(|(plant, count)| Some((plant.to_string(), count?)))
+        .collect();
+        Self {
+            source: "Open-Meteo".to_string(),
+            observed: from_timestamp(value.time),
+            pm2_5: value.pm2_5,
+            pm10: value.pm10,
+            o3: value.ozone,
+            no2: value.nitrogen_dioxide,
+            so2: value.sulphur_dioxide,
+            co: value.carbon_monoxide,
+            pollen,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn air(pm2_5: f32, pm10: f32, o3: f32) -> AirQuality {
+        AirQuality {
+            source: "test".to_string(),
+            pm2_5: Some(pm2_5),
+            pm10: Some(pm10),
+            o3: Some(o3),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_us_sub_index_breakpoints() {
+        assert_eq!(us_sub_index(0.0, &US_PM2_5), 0);
+        assert_eq!(us_sub_index(9.0, &US_PM2_5), 50);
+        assert_eq!(us_sub_index(9.1, &US_PM2_5), 51);
+        assert_eq!(us_sub_index(35.0, &US_PM2_5), 99);
+        assert_eq!(us_sub_index(100., &US_PM10), 73);
+        assert_eq!(us_sub_index(1000., &US_PM10), 500);
+    }
+
+    #[test]
+    fn test_us_concentration_units() {
+        // 100 µg/m³ of ozone is 50.9 ppb, truncated to 50
+        assert_eq!(us_concentration(Pollutant::O3, 100.), 50.);
+        // 10 000 µg/m³ of CO is 8.73 ppm, truncated to 8.7
+        assert_eq!(us_concentration(Pollutant::Co, 10_000.), 8.7);
+        assert_eq!(us_concentration(Pollutant::Pm2_5, 12.38), 12.3);
+        assert_eq!(us_concentration(Pollutant::Pm10, 54.9), 54.);
+    }
+
+    #[test]
+    fn test_us_aqi_takes_worst_pollutant() {
+        let aqi = air(35.0, 100., 100.).us_aqi().unwrap();
+        assert_eq!(aqi.value, 99);
+        assert_eq!(aqi.pollutant, Pollutant::Pm2_5);
+        assert_eq!(aqi.category(), "Moderate");
+        assert_eq!(aqi.to_string(), "99 Moderate (PM2.5)");
+    }
+
+    #[test]
+    fn test_eu_aqi_levels() {
+        let aqi = air(4., 50., 110.).eu_aqi().unwrap();
+        assert_eq!(aqi.value, 3);
+        assert_eq!(aqi.category(), "Moderate");
+        assert_eq!(
+            air(150., 10., 10.).eu_aqi().unwrap().category(),
+            "Extremely poor"
+        );
+        assert_eq!(air(5., 15., 60.).eu_aqi().unwrap().value, 1);
+    }
+
+    #[test]
+    fn test_no_concentrations_no_index() {
+        let empty = AirQuality::default();
+        assert!(empty.us_aqi().is_none());
+        assert!(empty.eu_aqi().is_none());
+    }
+}
@@ -0,0 +1,284 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+use clap::ValueEnum;
+use std::fmt;
+
+use super::models::nws::NwsAlert;
+use super::models::openweather_api::OpenWeatherAlert;
+use super::models::visual_crossing::VisualCrossingAlert;
+use super::models::weather_api::WeatherApiAlert;
+
+/// CAP severity of an alert, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum AlertSeverity {
+    Unknown,
+    Minor,
+    Moderate,
+    Severe,
+    Extreme,
+}
+
+impl AlertSeverity {
+    /// Severity named by a CAP `severity` value, `Unknown` for anything else.
+    pub fn parse(value: &str) -> Self {
+        <Self as ValueEnum>::from_str(value.trim(), true).unwrap_or(Self::Unknown)
+    }
+
+    /// Severity implied by an event name, for providers that relay alerts without one:
+    /// the Meteoalarm colour ("Orange wind warning") or the NWS-style kind ("Flood Watch").
+    /// `Unknown` when the name has neither.
+    pub fn from_event(event: &str) -> Self {
+        let event = event.to_lowercase();
+        let words: Vec<&str> = event
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let has = |names: &[&str]| words.iter().any(|word| names.contains(word));
+        if has(&["red"]) {
+            Self::Extreme
+        } else if has(&["orange", "amber"]) {
+            Self::Severe
+        } else if has(&["yellow"]) {
+            Self::Moderate
+        } else if has(&["warning", "warnings"]) {
+            Self::Severe
+        } else if has(&["watch"]) {
+            Self::Moderate
+        } else if has(&["advisory", "statement"]) {
+            Self::Minor
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Unknown => "Unknown",
+            Self::Minor => "Minor",
+            Self::Moderate => "Moderate",
+            Self::Severe => "Severe",
+            Self::Extreme => "Extreme",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Weather warning issued for a location, whichever provider relayed it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeatherAlert {
+    pub sender: Option<String>,
+    pub event: String,
+    pub severity: AlertSeverity,
+    pub onset: Option<DateTime<FixedOffset>>,
+    pub expires: Option<DateTime<FixedOffset>>,
+    pub description: String,
+}
+
+impl WeatherAlert {
+    /// Whether the alert is in effect at any time of `date`, in the alert's own offset.
+    pub fn covers(&self, date: NaiveDate) -> bool {
+        self.onset.is_none_or(|onset| onset.date_naive() <= date)
+            && self
+                .expires
+                .is_none_or(|expires| expires.date_naive() >= date)
+    }
+
+    /// One line naming the alert and when it ends, as shown above a daily report.
+    pub fn title(&self) -> String {
+        let mut title = format!("{} ({})", self.event, self.severity);
+        if let Some(sender) = &self.sender {
+            title.push_str(&format!(" from {}", sender));
+        }
+        if let Some(until) = self.expires {
+            title.push_str(&format!(", until {}", time(until)));
+        }
+        title
+    }
+}
+
+fn time(at: DateTime<FixedOffset>) -> String {
+    at.format("%Y-%m-%d %H:%M %:z").to_string()
+}
+
+impl fmt::Display for WeatherAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.event, self.severity)?;
+        if let Some(sender) = &self.sender {
+            write!(f, " from {}", sender)?;
+        }
+        match (self.onset, self.expires) {
+            (Some(onset), Some(until)) => write!(f, "\n{} to {}", time(onset), time(until))?,
+            (Some(onset), None) => write!(f, "\nfrom {}", time(onset))?,
+            (None, Some(until)) => write!(f, "\nuntil {}", time(until))?,
+            (None, None) => {}
+        }
+        if !self.description.is_empty() {
+            write!(f, "\n\n{}", self.description.trim())?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of alerts at or above `threshold`, the ones a script gating on warnings cares about.
+pub fn at_least(alerts: &[WeatherAlert], threshold: AlertSeverity) -> usize {
+    alerts
+        .iter()
+        .filter(|alert| alert.severity >= threshold)
+        .count()
+}
+
+fn from_timestamp(secs: i64) -> Option<DateTime<FixedOffset>> {
+    Utc.timestamp_opt(secs, 0)
+        .single()
+        .map(|at| at.fixed_offset())
+}
+
+/// Non-empty text, `None` otherwise.
+fn non_empty(text: Option<String>) -> Option<String> {
+    text.filter(|text| !text.trim().is_empty())
+}
+
+impl From<NwsAlert> for WeatherAlert {
+    fn from(value: NwsAlert) -> Self {
+        let description = match value.headline {
+            Some(headline) if !value.description.is_empty() => {
+                format!("{}\n\n{}", headline, value.description.trim())
+            }
+            Some(headline) => headline,
+            None => value.description,
+        };
+        Self {
+            sender: Some(value.sender_name),
+            event: value.event,
+            severity: AlertSeverity::parse(&value.severity),
+            onset: value.onset,
+            expires: value.ends.or(value.expires),
+            description,
+        }
+    }
+}
+
+/// OpenWeather relays national agencies without a severity, the event name implies one.
+impl From<OpenWeatherAlert> for WeatherAlert {
+    fn from(value: OpenWeatherAlert) -> Self {
+        Self {
+            sender: non_empty(Some(value.sender_name)),
+            severity: AlertSeverity::from_event(&value.event),
+            event: value.event,
+            onset: from_timestamp(value.start),
+            expires: from_timestamp(value.end),
+            description: value.description,
+        }
+    }
+}
+
+impl From<WeatherApiAlert> for WeatherAlert {
+    fn from(value: WeatherApiAlert) -> Self {
+        let event = non_empty(value.event)
+            .or(non_empty(value.headline.clone()))
+            .unwrap_or_else(|| "Weather alert".to_string());
+        Self {
+            sender: None,
+            event,
+            severity: value
+                .severity
+                .as_deref()
+                .map(AlertSeverity::parse)
+                .unwrap_or(AlertSeverity::Unknown),
+            onset: value.effective,
+            expires: value.expires,
+            description: non_empty(value.desc).or(value.headline).unwrap_or_default(),
+        }
+    }
+}
+
+/// Visual Crossing passes alerts on without a severity or sender, the event name implies
+/// the severity.
+impl From<VisualCrossingAlert> for WeatherAlert {
+    fn from(value: VisualCrossingAlert) -> Self {
+        Self {
+            sender: None,
+            severity: AlertSeverity::from_event(&value.event),
+            event: value.event,
+            onset: value.onset_epoch.and_then(from_timestamp),
+            expires: value.ends_epoch.and_then(from_timestamp),
+            description: non_empty(value.description)
+                .or(value.headline)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> Option<DateTime<FixedOffset>> {
+        Some(DateTime::parse_from_rfc3339(text).unwrap())
+    }
+
+    fn alert(severity: AlertSeverity) -> WeatherAlert {
+        WeatherAlert {
+            sender: Some("Met Office".to_string()),
+            event: "Yellow wind warning".to_string(),
+            severity,
+            onset: at("2025-01-23T21:00:00+00:00"),
+            expires: at("2025-01-25T06:00:00+00:00"),
+            description: "Strong winds may cause disruption.".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_severity_parse_and_order() {
+        assert_eq!(AlertSeverity::parse("Severe"), AlertSeverity::Severe);
+        assert_eq!(AlertSeverity::parse("moderate"), AlertSeverity::Moderate);
+        assert_eq!(AlertSeverity::parse(""), AlertSeverity::Unknown);
+        assert!(AlertSeverity::Extreme > AlertSeverity::Minor);
+        assert!(AlertSeverity::Unknown < AlertSeverity::Minor);
+    }
+
+    #[test]
+    fn test_severity_from_event() {
+        let severity = AlertSeverity::from_event;
+        assert_eq!(severity("Red warning for rain"), AlertSeverity::Extreme);
+        assert_eq!(severity("Amber wind warning"), AlertSeverity::Severe);
+        assert_eq!(
+            severity("Yellow warning for extreme heat"),
+            AlertSeverity::Moderate
+        );
+        assert_eq!(severity("Winter Storm Warning"), AlertSeverity::Severe);
+        assert_eq!(severity("Flood Watch"), AlertSeverity::Moderate);
+        assert_eq!(severity("Wind Advisory"), AlertSeverity::Minor);
+        assert_eq!(severity("Reduced visibility"), AlertSeverity::Unknown);
+        assert_eq!(severity("Strong wind"), AlertSeverity::Unknown);
+    }
+
+    #[test]
+    fn test_covers_every_day_of_the_alert() {
+        let alert = alert(AlertSeverity::Moderate);
+        let day = |d| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        assert!(!alert.covers(day(22)));
+        assert!(alert.covers(day(23)));
+        assert!(alert.covers(day(25)));
+        assert!(!alert.covers(day(26)));
+    }
+
+    #[test]
+    fn test_at_least() {
+        let alerts = [alert(AlertSeverity::Minor), alert(AlertSeverity::Severe)];
+        assert_eq!(at_least(&alerts, AlertSeverity::Unknown), 2);
+        assert_eq!(at_least(&alerts, AlertSeverity::Moderate), 1);
+        assert_eq!(at_least(&alerts, AlertSeverity::Extreme), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            alert(AlertSeverity::Moderate).to_string(),
+            "Yellow wind warning (Moderate) from Met Office\n\
+             2025-01-23 21:00 +00:00 to 2025-01-25 06:00 +00:00\n\n\
+             Strong winds may cause disruption."
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{Days, NaiveDate};
+use std::fmt;
+
+use super::error::{ProviderError, ProviderResult};
+
+/// Static description of what a provider can answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// How many days after today the forecast reaches.
+    pub forecast_days: u32,
+    /// How many days before today historical data is available, `None` if not supported.
+    pub history_days: Option<u32>,
+    pub hourly: bool,
+    pub alerts: bool,
+    pub air_quality: bool,
+    pub requires_key: bool,
+}
+
+impl ProviderCapabilities {
+    /// Checks that `date` is answerable relative to `today` without touching the network.
+    pub fn check_date(&self, date: NaiveDate, today: NaiveDate) -> ProviderResult<()> {
+        let first = today
+            .checked_sub_days(Days::new(self.history_days.unwrap_or_default().into()))
+            .unwrap_or(NaiveDate::MIN);
+        let last = today
+            .checked_add_days(Days::new(self.forecast_days.into()))
+            .unwrap_or(NaiveDate::MAX);
+        if date < first || date > last {
+            return Err(ProviderError::DateIsOutOfRange(format!(
+                "supported dates are {first} to {last}, got {date}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ProviderCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+        let history = match self.history_days {
+            Some(days) => format!("{days} days"),
+            None => "no".to_string(),
+        };
+        write!(
+            f,
+            "forecast: {} days | history: {} | hourly: {} | alerts: {} | air quality: {} | key required: {}",
+            self.forecast_days,
+            history,
+            yes_no(self.hourly),
+            yes_no(self.alerts),
+            yes_no(self.air_quality),
+            yes_no(self.requires_key),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPS: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 7,
+        history_days: Some(3),
+        hourly: true,
+        alerts: false,
+        air_quality: false,
+        requires_key: true,
+    };
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, d).unwrap()
+    }
+
+    #[test]
+    fn test_check_date_inside_window() {
+        assert!(CAPS.check_date(day(10), day(10)).is_ok());
+        assert!(CAPS.check_date(day(17), day(10)).is_ok());
+        assert!(CAPS.check_date(day(7), day(10)).is_ok());
+    }
+
+    #[test]
+    fn test_check_date_outside_window() {
+        assert!(matches!(
+            CAPS.check_date(day(18), day(10)),
+            Err(ProviderError::DateIsOutOfRange(_))
+        ));
+        assert!(matches!(
+            CAPS.check_date(day(6), day(10)),
+            Err(ProviderError::DateIsOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_date_without_history() {
+        let caps = ProviderCapabilities {
+            history_days: None,
+            ..CAPS
+        };
+        assert!(caps.check_date(day(9), day(10)).is_err());
+        assert!(caps.check_date(day(10), day(10)).is_ok());
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Intensity {
+    Light,
+    Moderate,
+    Heavy,
+}
+
+/// Provider-independent condition of a day, for rules, icons and translations. The
+/// provider's own wording stays in the report's summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeatherCondition {
+    Clear,
+    PartlyCloudy,
+    Cloudy,
+    Overcast,
+    /// Fog and mist.
+    Fog,
+    /// Haze, smoke, dust, sand and volcanic ash.
+    Haze,
+    Drizzle(Intensity),
+    Rain(Intensity),
+    FreezingRain(Intensity),
+    /// Sleet and ice pellets, and rain mixed with snow.
+    Sleet(Intensity),
+    Snow(Intensity),
+    Thunderstorm(Intensity),
+    Squall,
+    Tornado,
+}
+
+impl WeatherCondition {
+    /// OpenWeather condition id, see <https://openweathermap.org/weather-conditions>.
+    pub fn from_open_weather_id(id: u32) -> Option<Self> {
+        use Intensity::*;
+        let condition = match id {
+            200 | 210 | 230 => Self::Thunderstorm(Light),
+            202 | 212 | 232 => Self::Thunderstorm(Heavy),
+            200..=299 => Self::Thunderstorm(Moderate),
+            300 | 310 => Self::Drizzle(Light),
+            302 | 312 | 314 => Self::Drizzle(Heavy),
+            300..=399 => Self::Drizzle(Moderate),
+            511 => Self::FreezingRain(Moderate),
+            500 | 520 => Self::Rain(Light),
+            502..=504 | 522 => Self::Rain(Heavy),
+            500..=599 => Self::Rain(Moderate),
+            612 | 615 => Self::Sleet(Light),
+            611 | 613 | 616 => Self::Sleet(Moderate),
+            600 | 620 => Self::Snow(Light),
+            602 | 622 => Self::Snow(Heavy),
+            600..=699 => Self::Snow(Moderate),
+            701 | 741 => Self::Fog,
+            711 | 721 | 731 | 751 | 761 | 762 => Self::Haze,
+            771 => Self::Squall,
+            781 => Self::Tornado,
+            800 => Self::Clear,
+            801 | 802 => Self::PartlyCloudy,
+            803 => Self::Cloudy,
+            804 => Self::Overcast,
+            _ => return None,
+        };
+        Some(condition)
+    }
+
+    /// WeatherAPI condition code, see <https://www.weatherapi.com/docs/weather_conditions.json>.
+    pub fn from_weather_api_code(code: u32) -> Option<Self> {
+        use Intensity::*;
+        let condition = match code {
+            1000 => Self::Clear,
+            1003 => Self::PartlyCloudy,
+            1006 => Self::Cloudy,
+            1009 => Self::Overcast,
+            1030 | 1135 | 1147 => Self::Fog,
+            1063 | 1180 | 1183 | 1240 => Self::Rain(Light),
+            1186 | 1189 => Self::Rain(Moderate),
+            1192 | 1195 | 1243 | 1246 => Self::Rain(Heavy),
+            1066 | 1210 | 1213 | 1255 => Self::Snow(Light),
+            1114 | 1216 | 1219 => Self::Snow(Moderate),
+            1117 | 1222 | 1225 | 1258 => Self::Snow(Heavy),
+            1069 | 1204 | 1249 | 1261 => Self::Sleet(Light),
+            1237 => Self::Sleet(Moderate),
+            1207 | 1252 | 1264 => Self::Sleet(Heavy),
+            1072 | 1168 | 1198 => Self::FreezingRain(Light),
+            1171 | 1201 => Self::FreezingRain(Heavy),
+            1150 | 1153 => Self::Drizzle(Light),
+            1087 | 1273 | 1279 => Self::Thunderstorm(Light),
+            1276 | 1282 => Self::Thunderstorm(Heavy),
+            _ => return None,
+        };
+        Some(condition)
+    }
+
+    /// MET Norway symbol code such as `lightrainshowers_day` or `heavysnowandthunder`.
+    pub fn from_met_norway_symbol(symbol: &str) -> Option<Self> {
+        let code = symbol.split('_').next().unwrap_or(symbol);
+        let intensity = if code.starts_with("light") {
+            Intensity::Light
+        } else if code.starts_with("heavy") {
+            Intensity::Heavy
+        } else {
+            Intensity::Moderate
+        };
+        let condition = match code {
+            "clearsky" => Self::Clear,
+            "fair" | "partlycloudy" => Self::PartlyCloudy,
+            "cloudy" => Self::Cloudy,
+            "fog" => Self::Fog,
+            code if code.contains("thunder") => Self::Thunderstorm(intensity),
+            code if code.contains("sleet") => Self::Sleet(intensity),
+            code if code.contains("snow") => Self::Snow(intensity),
+            code if code.contains("rain") => Self::Rain(intensity),
+            _ => return None,
+        };
+        Some(condition)
+    }
+
+    pub fn intensity(&self) -> Option<Intensity> {
+        match self {
+            Self::Drizzle(intensity)
+            | Self::Rain(intensity)
+            | Self::FreezingRain(intensity)
+            | Self::Sleet(intensity)
+            | Self::Snow(intensity)
+            | Self::Thunderstorm(intensity) => Some(*intensity),
+            _ => None,
+        }
+    }
+
+    /// Whether anything falls, drizzle to thunderstorm.
+    pub fn is_precipitation(&self) -> bool {
+        self.intensity().is_some()
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Clear => "☀️",
+            Self::PartlyCloudy => "⛅",
+            Self::Cloudy | Self::Overcast => "☁️",
+            Self::Fog | Self::Haze => "🌫️",
+            Self::Drizzle(_) | Self::Rain(_) => "🌧️",
+            Self::FreezingRain(_) | Self::Sleet(_) => "🌨️",
+            Self::Snow(_) => "❄️",
+            Self::Thunderstorm(_) => "⛈️",
+            Self::Squall => "💨",
+            Self::Tornado => "🌪️",
+        }
+    }
+}
+
+impl fmt::Display for Intensity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Light => "light",
+            Self::Moderate => "moderate",
+            Self::Heavy => "heavy",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for WeatherCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Clear => "clear",
+            Self::PartlyCloudy => "partly cloudy",
+            Self::Cloudy => "cloudy",
+            Self::Overcast => "overcast",
+            Self::Fog => "fog",
+            Self::Haze => "haze",
+            Self::Drizzle(_) => "drizzle",
+            Self::Rain(_) => "rain",
+            Self::FreezingRain(_) => "freezing rain",
+            Self::Sleet(_) => "sleet",
+            Self::Snow(_) => "snow",
+            Self::Thunderstorm(_) => "thunderstorm",
+            Self::Squall => "squall",
+            Self::Tornado => "tornado",
+        };
+        match self.intensity() {
+            Some(intensity) => write!(f, "{} {}", intensity, name),
+            None => write!(f, "{}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Intensity::*;
+
+    #[test]
+    fn test_open_weather_ids() {
+        let condition = WeatherCondition::from_open_weather_id;
+        assert_eq!(condition(800), Some(WeatherCondition::Clear));
+        assert_eq!(condition(802), Some(WeatherCondition::PartlyCloudy));
+        assert_eq!(condition(500), Some(WeatherCondition::Rain(Light)));
+        assert_eq!(condition(503), Some(WeatherCondition::Rain(Heavy)));
+        assert_eq!(
+            condition(511),
+            Some(WeatherCondition::FreezingRain(Moderate))
+        );
+        assert_eq!(condition(616), Some(WeatherCondition::Sleet(Moderate)));
+        assert_eq!(condition(212), Some(WeatherCondition::Thunderstorm(Heavy)));
+        assert_eq!(
+            condition(221),
+            Some(WeatherCondition::Thunderstorm(Moderate))
+        );
+        assert_eq!(condition(741), Some(WeatherCondition::Fog));
+        assert_eq!(condition(999), None);
+    }
+
+    #[test]
+    fn test_weather_api_codes() {
+        let condition = WeatherCondition::from_weather_api_code;
+        assert_eq!(condition(1000), Some(WeatherCondition::Clear));
+        assert_eq!(condition(1063), Some(WeatherCondition::Rain(Light)));
+        assert_eq!(condition(1195), Some(WeatherCondition::Rain(Heavy)));
+        assert_eq!(condition(1117), Some(WeatherCondition::Snow(Heavy)));
+        assert_eq!(condition(1201), Some(WeatherCondition::FreezingRain(Heavy)));
+        assert_eq!(condition(1276), Some(WeatherCondition::Thunderstorm(Heavy)));
+        assert_eq!(condition(1), None);
+    }
+
+    #[test]
+    fn test_met_norway_symbols() {
+        let condition = WeatherCondition::from_met_norway_symbol;
+        assert_eq!(condition("clearsky_day"), Some(WeatherCondition::Clear));
+        assert_eq!(
+            condition("lightrainshowers_night"),
+            Some(WeatherCondition::Rain(Light))
+        );
+        assert_eq!(
+            condition("heavysnowandthunder"),
+            Some(WeatherCondition::Thunderstorm(Heavy))
+        );
+        assert_eq!(condition("sleet"), Some(WeatherCondition::Sleet(Moderate)));
+        assert_eq!(condition("unknown"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(WeatherCondition::Rain(Heavy).to_string(), "heavy rain");
+        assert_eq!(WeatherCondition::PartlyCloudy.to_string(), "partly cloudy");
+        assert!(WeatherCondition::Snow(Light).is_precipitation());
+        assert!(!WeatherCondition::Fog.is_precipitation());
+    }
+}
@@ -1,5 +1,44 @@
+use reqwest::header::HeaderMap;
+use std::fmt;
 use thiserror::Error;
 
+use crate::http::{HttpError, retry_after};
+
+/// Non-success HTTP answer from a provider, with the provider's own error code when it sent one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpFailure {
+    pub status: u16,
+    pub code: Option<String>,
+    pub message: String,
+    /// Seconds from the `Retry-After` header.
+    pub retry_after: Option<u64>,
+}
+
+impl HttpFailure {
+    pub fn new(status: u16, headers: &HeaderMap, code: Option<String>, message: String) -> Self {
+        Self {
+            status,
+            code,
+            message,
+            retry_after: retry_after(headers).map(|delay| delay.as_secs()),
+        }
+    }
+}
+
+impl fmt::Display for HttpFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "status {}", self.status)?;
+        if let Some(code) = &self.code {
+            write!(f, ", code {}", code)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(secs) = self.retry_after {
+            write!(f, " (retry after {}s)", secs)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ProviderError {
     #[error("request failed: {0}")]
@@ -8,14 +47,113 @@ pub enum ProviderError {
     #[error("failed to parse response: {0}")]
     ParseError(String), // JSON deserialization failed
 
-    #[error("failed with msg from provider: {0}")]
-    ProviderMsgError(String),
+    #[error("provider rejected the api key, {0}")]
+    Unauthorized(HttpFailure),
+
+    #[error("provider quota exceeded, {0}")]
+    QuotaExceeded(HttpFailure),
+
+    #[error("provider found nothing, {0}")]
+    NotFound(HttpFailure),
+
+    #[error("{0}")]
+    LocalQuotaExceeded(String), // refused before the request left
+
+    #[error("provider is unavailable, {0}")]
+    ServerError(HttpFailure),
+
+    #[error("failed with msg from provider, {0}")]
+    ProviderMsgError(HttpFailure),
 
     #[error("provided error is out of range for provider: {0}")]
     DateIsOutOfRange(String),
 
+    #[error("provider does not support {0}")]
+    Unsupported(String),
+
     #[error("failed to convert to common format response from provider: {0}, e: {1}")]
     ConvertionError(String, String), // other HTTP errors
 }
 
+impl ProviderError {
+    /// Classifies a failure by its HTTP status alone.
+    pub fn from_http(failure: HttpFailure) -> Self {
+        match failure.status {
+            401 | 403 => Self::Unauthorized(failure),
+            404 => Self::NotFound(failure),
+            429 => Self::QuotaExceeded(failure),
+            500..=599 => Self::ServerError(failure),
+            _ => Self::ProviderMsgError(failure),
+        }
+    }
+
+    /// Process exit code for this error, see README "Exit Codes".
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Unauthorized(_) => 4,
+            Self::QuotaExceeded(_) | Self::LocalQuotaExceeded(_) => 5,
+            Self::NotFound(_) => 6,
+            Self::DateIsOutOfRange(_) => 7,
+            Self::Unsupported(_) => 10,
+            Self::RequestFailed(_) | Self::ServerError(_) => 8,
+            Self::ParseError(_) | Self::ConvertionError(..) | Self::ProviderMsgError(_) => 1,
+        }
+    }
+}
+
+impl From<HttpError> for ProviderError {
+    fn from(err: HttpError) -> Self {
+        match err {
+            HttpError::Quota(e) => Self::LocalQuotaExceeded(e.to_string()),
+            e => Self::RequestFailed(e.to_string()),
+        }
+    }
+}
+
 pub type ProviderResult<T> = Result<T, ProviderError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderValue, RETRY_AFTER};
+
+    fn failure(status: u16) -> HttpFailure {
+        HttpFailure::new(status, &HeaderMap::new(), None, "msg".to_string())
+    }
+
+    #[test]
+    fn test_from_http_classifies_status() {
+        assert!(matches!(
+            ProviderError::from_http(failure(401)),
+            ProviderError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            ProviderError::from_http(failure(429)),
+            ProviderError::QuotaExceeded(_)
+        ));
+        assert!(matches!(
+            ProviderError::from_http(failure(404)),
+            ProviderError::NotFound(_)
+        ));
+        assert!(matches!(
+            ProviderError::from_http(failure(503)),
+            ProviderError::ServerError(_)
+        ));
+        assert!(matches!(
+            ProviderError::from_http(failure(400)),
+            ProviderError::ProviderMsgError(_)
+        ));
+    }
+
+    #[test]
+    fn test_retry_after_is_parsed() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        let failure = HttpFailure::new(429, &headers, Some("429".to_string()), "slow down".into());
+        assert_eq!(failure.retry_after, Some(120));
+        assert_eq!(
+            failure.to_string(),
+            "status 429, code 429: slow down (retry after 120s)"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use chrono::{Datelike, NaiveDate};
+use std::f32::consts::PI;
+use std::fs;
+use std::path::PathBuf;
+
+use super::ProviderCapabilities;
+use super::error::{ProviderError, ProviderResult};
+use super::models::fixture::{FixtureFile, FixtureReport};
+use super::provider_trait::WeatherProvider;
+use super::providers_report::ProvidersReport;
+
+const SUMMARIES: [&str; 6] = [
+    "Clear sky",
+    "Partly cloudy",
+    "Overcast",
+    "Light rain",
+    "Moderate rain",
+    "Thunderstorm",
+];
+
+/// Offline provider answering from a file of canned days, or from a seeded generator.
+pub struct Fixture {
+    path: Option<PathBuf>,
+    seed: u64,
+}
+
+impl Fixture {
+    /// Any date is answerable, a fixture file may still lack it.
+    pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 36_500,
+        history_days: Some(36_500),
+        hourly: false,
+        alerts: false,
+        air_quality: false,
+        requires_key: false,
+    };
+
+    pub fn new(path: Option<PathBuf>, seed: Option<u64>) -> Self {
+        Self {
+            path,
+            seed: seed.unwrap_or_default(),
+        }
+    }
+
+    fn load(path: &PathBuf) -> ProviderResult<FixtureFile> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| ProviderError::ParseError(format!("{}: {}", path.display(), e)))?;
+        let parsed = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&contents).map_err(|e| e.to_string())
+        } else {
+            serde_json::from_str(&contents).map_err(|e| e.to_string())
+        };
+        parsed.map_err(|e| ProviderError::ParseError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Deterministic day for `seed`, location and date: seasonal temperature plus noise.
+    fn synthesize(&self, latitude: f64, longitude: f64, date: NaiveDate) -> ProvidersReport {
+        let mut rng = SplitMix64::new(
+            self.seed
+                ^ (latitude * 1000.).round() as i64 as u64
+                ^ ((longitude * 1000.).round() as i64 as u64).rotate_left(21)
+                ^ (date.num_days_from_ce() as u64).rotate_left(42),
+        );
+        // warmest around day 200 in the north, day 20 in the south
+        let peak = if latitude >= 0. { 200. } else { 20. };
+        let season = (2. * PI * (date.ordinal() as f32 - peak) / 365.).cos();
+        let mean = 27. - 0.4 * latitude.abs() as f32 + 10. * season * (latitude.abs() as f32 / 60.);
+        let avg_temp = mean + rng.range(-3., 3.);
+        let spread = rng.range(3., 8.);
+        let pop = rng.range(0., 1.);
+        let summary = SUMMARIES[((pop * SUMMARIES.len() as f32) as usize).min(SUMMARIES.len() - 1)];
+        ProvidersReport::from(FixtureReport {
+            min_temp: Some(avg_temp - spread),
+            max_temp: Some(avg_temp + spread),
+            avg_temp: Some(avg_temp),
+            pop: Some(pop),
+            summary: Some(summary.to_string()),
+            wind_speed: Some(rng.range(0., 12.)),
+            humidity: Some(rng.range(30., 95.)),
+            wind_direction: Some(rng.range(0., 360.) as i32),
+            ..FixtureReport::default()
+        })
+    }
+}
+
+impl WeatherProvider for Fixture {
+    fn get_weather(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+    ) -> ProviderResult<ProvidersReport> {
+        let Some(path) = &self.path else {
+            return Ok(self.synthesize(latitude, longitude, date));
+        };
+        Self::load(path)?
+            .days
+            .into_iter()
+            .find(|day| day.date == date)
+            .map(|day| ProvidersReport::from(day.report))
+            .ok_or(ProviderError::DateIsOutOfRange(format!(
+                "no {} in fixture {}",
+                date,
+                path.display()
+            )))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        Self::CAPABILITIES
+    }
+}
+
+/// Small seeded generator, enough for plausible demo data.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        let unit = (self.next() >> 40) as f32 / (1u64 << 24) as f32;
+        min + (max - min) * unit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+    }
+
+    #[test]
+    fn test_synthetic_is_deterministic() {
+        let provider = Fixture::new(None, Some(42));
+        let first = provider.get_weather(50.45, 30.52, date()).unwrap();
+        let second = provider.get_weather(50.45, 30.52, date()).unwrap();
+        assert_eq!(first.to_string(), second.to_string());
+    }
+
+    #[test]
+    fn test_synthetic_depends_on_seed() {
+        let a = Fixture::new(None, Some(1)).get_weather(50.45, 30.52, date());
+        let b = Fixture::new(None, Some(2)).get_weather(50.45, 30.52, date());
+        assert_ne!(a.unwrap().to_string(), b.unwrap().to_string());
+    }
+
+    #[test]
+    fn test_missing_fixture_file() {
+        let provider = Fixture::new(Some(PathBuf::from("/nonexistent/days.toml")), None);
+        assert!(matches!(
+            provider.get_weather(0., 0., date()),
+            Err(ProviderError::ParseError(_))
+        ));
+    }
+}
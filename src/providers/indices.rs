@@ -0,0 +1,348 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt;
+
+/// Heat-stress and cold-exposure indices derived from a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexKind {
+    /// NWS heat index, from the day's high
+    HeatIndex,
+    /// NWS and Environment Canada wind chill, from the day's low
+    WindChill,
+    /// Environment Canada humidex, from the day's high
+    Humidex,
+    /// Steadman's apparent temperature without radiation, from the day's mean
+    ApparentTemperature,
+    /// Approximate wet-bulb globe temperature, from the day's high
+    Wbgt,
+}
+
+impl IndexKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::HeatIndex => "Heat index",
+            Self::WindChill => "Wind chill",
+            Self::Humidex => "Humidex",
+            Self::ApparentTemperature => "Apparent temp",
+            Self::Wbgt => "WBGT (approx)",
+        }
+    }
+}
+
+/// Band of an index on a scale shared by all of them, to compare and filter across
+/// indices. `None` is the index's no-risk band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Level {
+    None,
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+    Extreme,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Index {
+    #[serde(rename = "index")]
+    pub kind: IndexKind,
+    /// °C, except the humidex which has no unit.
+    pub value: f32,
+    /// Name of the band on the index's own scale, e.g. `extreme caution`.
+    pub category: &'static str,
+    pub level: Level,
+}
+
+/// Heat index bands, NWS.
+const HEAT_INDEX: [(f32, &str, Level); 5] = [
+    (f32::MIN, "no risk", Level::None),
+    (26.7, "caution", Level::Low),
+    (32.2, "extreme caution", Level::Moderate),
+    (39.4, "danger", Level::High),
+    (51.7, "extreme danger", Level::Extreme),
+];
+
+/// Frostbite risk bands, Environment Canada.
+const WIND_CHILL: [(f32, &str, Level); 6] = [
+    (f32::MIN, "extreme risk", Level::Extreme),
+    (-54.5, "severe risk", Level::VeryHigh),
+    (-47.5, "very high risk", Level::High),
+    (-39.5, "high risk", Level::Moderate),
+    (-27.5, "moderate risk", Level::Low),
+    (-9.5, "low risk", Level::None),
+];
+
+/// Comfort bands, Environment Canada.
+const HUMIDEX: [(f32, &str, Level); 5] = [
+    (f32::MIN, "no discomfort", Level::None),
+    (30., "some discomfort", Level::Low),
+    (40., "great discomfort", Level::Moderate),
+    (46., "dangerous", Level::High),
+    (54., "heat stroke imminent", Level::Extreme),
+];
+
+/// Thermal stress bands of the UTCI, applied to the apparent temperature.
+const APPARENT_TEMPERATURE: [(f32, &str, Level); 10] = [
+    (f32::MIN, "extreme cold stress", Level::Extreme),
+    (-40., "very strong cold stress", Level::VeryHigh),
+    (-27., "strong cold stress", Level::High),
+    (-13., "moderate cold stress", Level::Moderate),
+    (0., "slight cold stress", Level::Low),
+    (9., "no thermal stress", Level::None),
+    (26., "moderate heat stress", Level::Moderate),
+    (32., "strong heat stress", Level::High),
+    (38., "very strong heat stress", Level::VeryHigh),
+    (46., "extreme heat stress", Level::Extreme),
+];
+
+/// Activity flags of the US Army and ACSM guidance.
+const WBGT: [(f32, &str, Level); 5] = [
+    (f32::MIN, "white flag", Level::None),
+    (27.8, "green flag", Level::Low),
+    (29.4, "yellow flag", Level::Moderate),
+    (31.1, "red flag", Level::High),
+    (32.2, "black flag", Level::Extreme),
+];
+
+/// The band starting at or below `value`, bands sorted by their lower bound.
+fn band(bands: &[(f32, &'static str, Level)], value: f32) -> (&'static str, Level) {
+    let (_, category, level) = bands
+        .iter()
+        .rev()
+        .find(|(lower, ..)| value >= *lower)
+        .unwrap_or(&bands[0]);
+    (*category, *level)
+}
+
+/// Report fields the indices are computed from, in canonical units. An index whose
+/// inputs are missing is skipped.
+#[derive(Debug, Clone, Copy)]
+pub struct Inputs {
+    pub max_temp: f32,
+    pub min_temp: f32,
+    pub avg_temp: f32,
+    /// %, the day's mean.
+    pub humidity: Option<f32>,
+    /// m/sec.
+    pub wind_speed: Option<f32>,
+    pub dew_point: Option<f32>,
+}
+
+impl Inputs {
+    /// Water vapour pressure in hPa, steadier over a day than the relative humidity. From
+    /// the dew point where known, the mean humidity at the mean temperature otherwise.
+    fn vapour_pressure(&self) -> Option<f32> {
+        match self.dew_point {
+            Some(dew_point) => Some(saturation_pressure(dew_point)),
+            None => Some(saturation_pressure(self.avg_temp) * self.humidity? / 100.),
+        }
+    }
+
+    /// Relative humidity at `temp`, for the same vapour pressure.
+    fn humidity_at(&self, temp: f32) -> Option<f32> {
+        Some((self.vapour_pressure()? / saturation_pressure(temp) * 100.).min(100.))
+    }
+}
+
+/// Saturation vapour pressure over water in hPa, Magnus formula.
+fn saturation_pressure(temp: f32) -> f32 {
+    6.105 * (17.27 * temp / (237.7 + temp)).exp()
+}
+
+impl Index {
+    /// `kind` for the day described by `inputs`, `None` without the inputs it needs or
+    /// outside the index's range: the heat index below 26.7°C, the wind chill above 10°C or
+    /// in calm air, the humidex below 20°C.
+    pub fn compute(kind: IndexKind, inputs: &Inputs) -> Option<Self> {
+        let (value, bands): (f32, &[_]) = match kind {
+            IndexKind::HeatIndex => (
+                heat_index(inputs.max_temp, inputs.humidity_at(inputs.max_temp)?)?,
+                &HEAT_INDEX,
+            ),
+            IndexKind::WindChill => (
+                wind_chill(inputs.min_temp, inputs.wind_speed?)?,
+                &WIND_CHILL,
+            ),
+            IndexKind::Humidex => (
+                humidex(inputs.max_temp, inputs.vapour_pressure()?)?,
+                &HUMIDEX,
+            ),
+            IndexKind::ApparentTemperature => (
+                apparent_temperature(
+                    inputs.avg_temp,
+                    inputs.vapour_pressure()?,
+                    inputs.wind_speed?,
+                ),
+                &APPARENT_TEMPERATURE,
+            ),
+            IndexKind::Wbgt => (wbgt(inputs.max_temp, inputs.vapour_pressure()?), &WBGT),
+        };
+        let (category, level) = band(bands, value);
+        Some(Self {
+            kind,
+            value,
+            category,
+            level,
+        })
+    }
+
+    /// Every index in range for the day.
+    pub fn all(inputs: &Inputs) -> Vec<Self> {
+        IndexKind::value_variants()
+            .iter()
+            .filter_map(|kind| Self::compute(*kind, inputs))
+            .collect()
+    }
+}
+
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            IndexKind::Humidex => write!(f, "{:.0}", self.value)?,
+            _ => write!(f, "{:.1}°C", self.value)?,
+        }
+        write!(f, " ({})", self.category)
+    }
+}
+
+/// NWS heat index: Rothfusz regression with its low and high humidity adjustments, and
+/// Steadman's simpler formula where that gives under 80°F.
+pub fn heat_index(temp: f32, humidity: f32) -> Option<f32> {
+    if temp < 26.7 {
+        return None;
+    }
+    let (t, rh) = (temp * 9. / 5. + 32., humidity);
+    let simple = 0.5 * (t + 61. + (t - 68.) * 1.2 + rh * 0.094);
+    let fahrenheit = if (simple + t) / 2. < 80. {
+        simple
+    } else {
+        let mut hi = -42.379 + 2.049_015_3 * t + 10.143_331 * rh
+            - 0.224_755_42 * t * rh
+            - 0.006_837_83 * t * t
+            - 0.054_817_17 * rh * rh
+            + 0.001_228_74 * t * t * rh
+            + 0.000_852_82 * t * rh * rh
+            - 0.000_001_99 * t * t * rh * rh;
+        if rh < 13. && (80. ..=112.).contains(&t) {
+            hi -= (13. - rh) / 4. * ((17. - (t - 95.).abs()) / 17.).sqrt();
+        } else if rh > 85. && (80. ..=87.).contains(&t) {
+            hi += (rh - 85.) / 10. * (87. - t) / 5.;
+        }
+        hi
+    };
+    Some((fahrenheit - 32.) * 5. / 9.)
+}
+
+/// Wind chill index of 2001 from the 10 m wind in m/sec, defined at 10°C and below with
+/// more than 4.8 km/h of wind.
+pub fn wind_chill(temp: f32, wind_speed: f32) -> Option<f32> {
+    let kph = wind_speed * 3.6;
+    if temp > 10. || kph <= 4.8 {
+        return None;
+    }
+    let v = kph.powf(0.16);
+    Some(13.12 + 0.6215 * temp - 11.37 * v + 0.3965 * temp * v)
+}
+
+/// Humidex from the vapour pressure in hPa, only reported from 20°C.
+pub fn humidex(temp: f32, vapour_pressure: f32) -> Option<f32> {
+    (temp >= 20.).then_some(temp + 0.5555 * (vapour_pressure - 10.))
+}
+
+/// Apparent temperature of the Australian Bureau of Meteorology, shade and no radiation.
+pub fn apparent_temperature(temp: f32, vapour_pressure: f32, wind_speed: f32) -> f32 {
+    temp + 0.33 * vapour_pressure - 0.70 * wind_speed - 4.00
+}
+
+/// Wet-bulb globe temperature for moderate sun and light wind, the Bureau of
+/// Meteorology approximation. Direct sun can add several degrees.
+pub fn wbgt(temp: f32, vapour_pressure: f32) -> f32 {
+    0.567 * temp + 0.393 * vapour_pressure + 3.94
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(max_temp: f32, min_temp: f32, humidity: f32, wind_speed: f32) -> Inputs {
+        Inputs {
+            max_temp,
+            min_temp,
+            avg_temp: (max_temp + min_temp) / 2.,
+            humidity: Some(humidity),
+            wind_speed: Some(wind_speed),
+            dew_point: None,
+        }
+    }
+
+    #[test]
+    fn test_heat_index_matches_nws_table() {
+        // 90°F at 70% reads 106°F in the NWS table, 96°F at 20% reads 93°F
+        let hi = heat_index(32.22, 70.).unwrap();
+        assert!((hi - 41.1).abs() < 0.3, "{hi}");
+        let hi = heat_index(35.56, 20.).unwrap();
+        assert!((hi - 33.9).abs() < 0.3, "{hi}");
+        assert_eq!(heat_index(25., 90.), None);
+    }
+
+    #[test]
+    fn test_wind_chill_matches_environment_canada_table() {
+        // -20°C in 30 km/h of wind reads -33
+        let wc = wind_chill(-20., 30. / 3.6).unwrap();
+        assert!((wc - -32.6).abs() < 0.1, "{wc}");
+        assert_eq!(wind_chill(12., 10.), None);
+        assert_eq!(wind_chill(-5., 1.), None);
+    }
+
+    #[test]
+    fn test_humidex_and_wbgt() {
+        // 31°C with a 15°C dew point is a humidex of 35
+        let day = Inputs {
+            dew_point: Some(15.),
+            ..inputs(31., 18., 50., 2.)
+        };
+        let humidex = Index::compute(IndexKind::Humidex, &day).unwrap();
+        assert!((humidex.value - 35.).abs() < 0.5, "{humidex:?}");
+        assert_eq!(humidex.category, "some discomfort");
+        let wbgt = Index::compute(IndexKind::Wbgt, &day).unwrap();
+        assert!((wbgt.value - 28.2).abs() < 0.2, "{wbgt:?}");
+        assert_eq!(wbgt.level, Level::Low);
+    }
+
+    #[test]
+    fn test_categories() {
+        let hot = Index::compute(IndexKind::HeatIndex, &inputs(38., 26., 60., 2.)).unwrap();
+        assert_eq!(hot.category, "danger");
+        assert_eq!(hot.to_string(), format!("{:.1}°C (danger)", hot.value));
+        let cold = Index::compute(IndexKind::WindChill, &inputs(-15., -30., 80., 8.)).unwrap();
+        assert_eq!((cold.category, cold.level), ("very high risk", Level::High));
+        let mild = Index::all(&inputs(20., 12., 60., 3.));
+        // neither the heat index nor the wind chill applies
+        assert_eq!(mild.len(), 3, "{mild:?}");
+        assert!(mild.iter().all(|index| index.level == Level::None));
+    }
+
+    #[test]
+    fn test_missing_inputs_skip_their_indices() {
+        let calm_unknown = Inputs {
+            wind_speed: None,
+            ..inputs(-5., -12., 80., 8.)
+        };
+        let kinds: Vec<_> = Index::all(&calm_unknown)
+            .iter()
+            .map(|index| index.kind)
+            .collect();
+        assert_eq!(kinds, [IndexKind::Wbgt]);
+        let dry_unknown = Inputs {
+            humidity: None,
+            ..inputs(33., 22., 50., 3.)
+        };
+        assert!(Index::all(&dry_unknown).is_empty());
+        let with_dew_point = Inputs {
+            dew_point: Some(20.),
+            ..dry_unknown
+        };
+        assert_eq!(Index::all(&with_dew_point).len(), 4);
+    }
+}
@@ -0,0 +1,465 @@
+use chrono::{DateTime, Local, NaiveDate};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use urlencoding::encode;
+
+use super::ProviderCapabilities;
+use super::error::{HttpFailure, ProviderError, ProviderResult};
+use super::models::json_api::JsonApiDay;
+use super::provider_trait::WeatherProvider;
+use super::providers_report::ProvidersReport;
+use super::units::{FractionUnit, SpeedUnit, TemperatureUnit};
+use crate::configs::ApiKey;
+use crate::http::HttpClient;
+use crate::redact;
+
+/// A REST source described in config: a URL template and JSON pointers into its answer.
+///
+/// The URL template and every pointer may use `{lat}`, `{lon}`, `{date}` (YYYY-MM-DD),
+/// `{day}` (days from today, negative in the past) and, in the URL only, `{key}`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JsonApiSpec {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<ApiKey>,
+    /// Array of days to search for `date_field` equal to the requested date.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<String>,
+    /// Date of a day inside `days`, a `YYYY-MM-DD...` string or unix seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_field: Option<String>,
+    pub fields: JsonFields,
+    #[serde(default)]
+    pub units: JsonUnits,
+    #[serde(default)]
+    pub errors: JsonErrors,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forecast_days: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_days: Option<u32>,
+}
+
+/// Never prints the api keys.
+impl fmt::Debug for JsonApiSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonApi")
+            .field("url", &self.url)
+            .field("api_key", &self.api_key.as_ref().map(|_| redact::REDACTED))
+            .field("api_keys", &self.api_keys)
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
+
+/// JSON pointers (RFC 6901) of the report fields, relative to the selected day.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JsonFields {
+    pub min_temp: String,
+    pub max_temp: String,
+    /// Mean of `min_temp` and `max_temp` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avg_temp: Option<String>,
+    pub pop: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub wind_speed: String,
+    pub humidity: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wind_direction: Option<String>,
+}
+
+/// Units the source answers in, converted to °C, m/s and a 0-1 probability.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct JsonUnits {
+    #[serde(default)]
+    pub temperature: TemperatureUnit,
+    #[serde(default)]
+    pub wind_speed: SpeedUnit,
+    #[serde(default)]
+    pub pop: FractionUnit,
+}
+
+/// Where the source puts its error code and message, and which codes mean what.
+///
+/// Codes are compared as strings, so `401` and `"401"` match alike. A 200 answer is
+/// an error when its code is one of the listed ones, or differs from `success`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct JsonErrors {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Code of a good answer, such as OpenWeather's `"cod": "200"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unauthorized: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quota_exceeded: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_found: Vec<String>,
+}
+
+impl JsonApiSpec {
+    pub fn new(url: String, fields: JsonFields) -> Self {
+        Self {
+            url,
+            api_key: None,
+            api_keys: Vec::new(),
+            days: None,
+            date_field: None,
+            fields,
+            units: JsonUnits::default(),
+            errors: JsonErrors::default(),
+            forecast_days: None,
+            history_days: None,
+        }
+    }
+}
+
+/// A flat document using the report's own field names.
+impl Default for JsonFields {
+    fn default() -> Self {
+        Self {
+            min_temp: "/min_temp".to_string(),
+            max_temp: "/max_temp".to_string(),
+            avg_temp: Some("/avg_temp".to_string()),
+            pop: "/pop".to_string(),
+            summary: Some("/summary".to_string()),
+            wind_speed: "/wind_speed".to_string(),
+            humidity: "/humidity".to_string(),
+            wind_direction: None,
+        }
+    }
+}
+
+pub struct JsonApi {
+    spec: JsonApiSpec,
+    api_key: String,
+    client: HttpClient,
+}
+
+impl JsonApi {
+    /// Any date is requested, the source answers for what it has.
+    pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 36_500,
+        history_days: Some(36_500),
+        hourly: false,
+        alerts: false,
+        air_quality: false,
+        requires_key: false,
+    };
+
+    pub fn new(spec: JsonApiSpec, api_key: String, client: HttpClient) -> Self {
+        Self {
+            spec,
+            api_key,
+            client,
+        }
+    }
+
+    fn expand(&self, template: &str, latitude: f64, longitude: f64, date: NaiveDate) -> String {
+        let today = Local::now().naive_local().date();
+        template
+            .replace("{lat}", &latitude.to_string())
+            .replace("{lon}", &longitude.to_string())
+            .replace("{date}", &date.to_string())
+            .replace("{day}", &(date - today).num_days().to_string())
+    }
+
+    fn prepare_url(&self, latitude: f64, longitude: f64, date: NaiveDate) -> String {
+        self.expand(&self.spec.url, latitude, longitude, date)
+            .replace("{key}", &encode(&self.api_key))
+    }
+
+    /// The element of `days` whose `date_field` is `date`, or the whole document.
+    fn select_day<'a>(&self, body: &'a Value, date: NaiveDate) -> ProviderResult<&'a Value> {
+        let (Some(days), Some(date_field)) = (&self.spec.days, &self.spec.date_field) else {
+            return Ok(body);
+        };
+        let days = body
+            .pointer(days)
+            .and_then(Value::as_array)
+            .ok_or_else(|| missing(days))?;
+        days.iter()
+            .find(|day| {
+                day.pointer(date_field)
+                    .is_some_and(|value| is_date(value, date))
+            })
+            .ok_or(ProviderError::DateIsOutOfRange(format!(
+                "json api has no {}",
+                date
+            )))
+    }
+
+    fn parse_report(
+        &self,
+        day: &Value,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+    ) -> ProviderResult<ProvidersReport> {
+        let fields = &self.spec.fields;
+        let pointer = |path: &str| self.expand(path, latitude, longitude, date);
+        let number = |path: &str| number_at(day, &pointer(path));
+        let optional = |path: &Option<String>| path.as_deref().map(number).transpose();
+        let summary = match &fields.summary {
+            Some(path) => Some(
+                day.pointer(&pointer(path))
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| missing(path))?
+                    .to_string(),
+            ),
+            None => None,
+        };
+        Ok(ProvidersReport::from(JsonApiDay {
+            min_temp: number(&fields.min_temp)?,
+            max_temp: number(&fields.max_temp)?,
+            avg_temp: optional(&fields.avg_temp)?,
+            pop: number(&fields.pop)?,
+            summary,
+            wind_speed: number(&fields.wind_speed)?,
+            humidity: number(&fields.humidity)?,
+            wind_direction: optional(&fields.wind_direction)?,
+            units: self.spec.units,
+        }))
+    }
+
+    /// Error code found in `body`, when the spec says where to look.
+    fn error_code(&self, body: &Value) -> Option<String> {
+        let value = body.pointer(self.spec.errors.code.as_deref()?)?;
+        match value {
+            Value::String(code) => Some(code.clone()),
+            Value::Null => None,
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Whether a 200 answer still reports an error in its body.
+    fn is_error_answer(&self, body: &Value) -> bool {
+        let errors = &self.spec.errors;
+        let Some(code) = self.error_code(body) else {
+            return false;
+        };
+        [
+            &errors.unauthorized,
+            &errors.quota_exceeded,
+            &errors.not_found,
+        ]
+        .iter()
+        .any(|codes| codes.contains(&code))
+            || errors
+                .success
+                .as_ref()
+                .is_some_and(|success| *success != code)
+    }
+
+    fn parse_error(&self, status: u16, headers: &HeaderMap, body: &str) -> ProviderError {
+        let errors = &self.spec.errors;
+        let json: Value = serde_json::from_str(body).unwrap_or_default();
+        let code = self.error_code(&json);
+        let message = errors
+            .message
+            .as_deref()
+            .and_then(|path| json.pointer(path))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or(body.to_string());
+        let failure = HttpFailure::new(status, headers, code.clone(), message);
+        let listed = |codes: &[String]| code.as_ref().is_some_and(|code| codes.contains(code));
+        if listed(&errors.unauthorized) {
+            ProviderError::Unauthorized(failure)
+        } else if listed(&errors.quota_exceeded) {
+            ProviderError::QuotaExceeded(failure)
+        } else if listed(&errors.not_found) {
+            ProviderError::NotFound(failure)
+        } else {
+            ProviderError::from_http(failure)
+        }
+    }
+}
+
+impl WeatherProvider for JsonApi {
+    fn get_weather(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+    ) -> ProviderResult<ProvidersReport> {
+        let response = self
+            .client
+            .get(&self.prepare_url(latitude, longitude, date))?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response
+            .text()
+            .map_err(|e| ProviderError::RequestFailed(e.without_url().to_string()))?;
+        if !(200..300).contains(&status) {
+            return Err(self.parse_error(status, &headers, &body));
+        }
+        let json: Value = serde_json::from_str(&body)
+            .map_err(|e| ProviderError::ParseError(format!("json api: {}", e)))?;
+        if self.is_error_answer(&json) {
+            return Err(self.parse_error(status, &headers, &body));
+        }
+        let day = self.select_day(&json, date)?;
+        self.parse_report(day, latitude, longitude, date)
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            forecast_days: self
+                .spec
+                .forecast_days
+                .unwrap_or(Self::CAPABILITIES.forecast_days),
+            history_days: self.spec.history_days.or(Self::CAPABILITIES.history_days),
+            requires_key: self.spec.api_key.is_some(),
+            ..Self::CAPABILITIES
+        }
+    }
+}
+
+fn missing(path: &str) -> ProviderError {
+    ProviderError::ConvertionError("json api".to_string(), format!("nothing at {}", path))
+}
+
+/// Number at `path`, numeric strings included.
+fn number_at(value: &Value, path: &str) -> ProviderResult<f32> {
+    let value = value.pointer(path).ok_or_else(|| missing(path))?;
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+        .map(|number| number as f32)
+        .ok_or_else(|| {
+            ProviderError::ConvertionError(
+                "json api".to_string(),
+                format!("{} is not a number: {}", path, value),
+            )
+        })
+}
+
+fn is_date(value: &Value, date: NaiveDate) -> bool {
+    match value {
+        Value::String(text) => text.starts_with(&date.to_string()),
+        Value::Number(secs) => secs
+            .as_i64()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+            .is_some_and(|dt| dt.date_naive() == date),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpSettings;
+
+    const SPEC: &str = r#"
+        url = "https://api.example.test/v1/daily?lat={lat}&lon={lon}&key={key}"
+        api_key = "json_api_test_key"
+        days = "/days"
+        date_field = "/datetime"
+
+        [fields]
+        min_temp = "/tempmin"
+        max_temp = "/tempmax"
+        pop = "/precipprob"
+        summary = "/conditions"
+        wind_speed = "/windspeed"
+        humidity = "/humidity"
+        wind_direction = "/winddir"
+
+        [units]
+        temperature = "fahrenheit"
+        wind_speed = "mph"
+        pop = "percent"
+
+        [errors]
+        code = "/error/code"
+        message = "/error/message"
+        unauthorized = ["1001"]
+    "#;
+
+    fn provider() -> JsonApi {
+        let spec: JsonApiSpec = toml::from_str(SPEC).unwrap();
+        let client = HttpClient::new(&HttpSettings::default()).unwrap();
+        JsonApi::new(spec, "json_api_test_key".to_string(), client)
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+    }
+
+    #[test]
+    fn test_prepare_url() {
+        assert_eq!(
+            provider().prepare_url(50.45, 30.52, date()),
+            "https://api.example.test/v1/daily?lat=50.45&lon=30.52&key=json_api_test_key"
+        );
+    }
+
+    #[test]
+    fn test_parse_report_converts_units() {
+        let body: Value = serde_json::from_str(
+            r#"{"days":[
+                {"datetime":"2025-06-30","tempmin":50,"tempmax":60},
+                {"datetime":"2025-07-01","tempmin":50,"tempmax":68,"precipprob":"40",
+                 "conditions":"Rain","windspeed":10,"humidity":70,"winddir":181.6}]}"#,
+        )
+        .unwrap();
+        let provider = provider();
+        let day = provider.select_day(&body, date()).unwrap();
+        let report = provider
+            .parse_report(day, 0., 0., date())
+            .unwrap()
+            .to_string();
+        assert!(
+            report.contains("min 10.0°C | max 20.0°C | avg 15.0°C"),
+            "{report}"
+        );
+        assert!(report.contains("Precipitation : 40.0%"), "{report}");
+        assert!(
+            report.contains("4.5 m/sec (Beaufort 3, gentle breeze) from S 182° ↑"),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn test_missing_day() {
+        let body: Value = serde_json::from_str(r#"{"days":[{"datetime":1751241600}]}"#).unwrap();
+        assert!(matches!(
+            provider().select_day(&body, date()),
+            Err(ProviderError::DateIsOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_error_code_mapping() {
+        let body = r#"{"error":{"code":1001,"message":"bad key"}}"#;
+        let err = provider().parse_error(400, &HeaderMap::new(), body);
+        assert!(
+            matches!(err, ProviderError::Unauthorized(ref f) if f.message == "bad key"),
+            "{err}"
+        );
+        let err = provider().parse_error(503, &HeaderMap::new(), "down");
+        assert!(matches!(err, ProviderError::ServerError(_)));
+    }
+
+    #[test]
+    fn test_error_code_in_good_answer() {
+        let mut provider = provider();
+        let answer = |text: &str| serde_json::from_str::<Value>(text).unwrap();
+        assert!(provider.is_error_answer(&answer(r#"{"error":{"code":1001}}"#)));
+        assert!(!provider.is_error_answer(&answer(r#"{"error":{"code":1}}"#)));
+        assert!(!provider.is_error_answer(&answer(r#"{"days":[]}"#)));
+        provider.spec.errors.code = Some("/cod".to_string());
+        provider.spec.errors.success = Some("200".to_string());
+        assert!(!provider.is_error_answer(&answer(r#"{"cod":"200"}"#)));
+        assert!(!provider.is_error_answer(&answer(r#"{"cod":200}"#)));
+        assert!(provider.is_error_answer(&answer(r#"{"cod":"404"}"#)));
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::NaiveDate;
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+
+use super::ProviderCapabilities;
+use super::error::{HttpFailure, ProviderError, ProviderResult};
+use super::models::met_norway::MetNorwayForecast;
+use super::provider_trait::WeatherProvider;
+use super::providers_report::ProvidersReport;
+use crate::http::HttpClient;
+use crate::http_cache::ResponseCache;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.met.no/weatherapi";
+
+/// MET Norway Locationforecast 2.0, keyless but strict about its terms of service:
+/// an identifying User-Agent, at most 4 coordinate decimals, and no request before
+/// the last answer `Expires`.
+pub struct MetNorway {
+    user_agent: Option<String>,
+    base_url: String,
+    client: HttpClient,
+    cache: ResponseCache,
+}
+
+impl MetNorway {
+    /// The forecast reaches 9-10 days ahead, there is no history.
+    pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 9,
+        history_days: Some(0),
+        hourly: true,
+        alerts: false,
+        air_quality: false,
+        requires_key: false,
+    };
+
+    pub fn new(user_agent: Option<String>, base_url: Option<String>, client: HttpClient) -> Self {
+        let base_url = base_url.unwrap_or(DEFAULT_BASE_URL.to_string());
+        Self {
+            user_agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+            cache: ResponseCache::default(),
+        }
+    }
+
+    /// Same provider keeping its answers in `cache`.
+    pub fn with_cache(self, cache: ResponseCache) -> Self {
+        Self { cache, ..self }
+    }
+
+    fn prepare_url(&self, latitude: f64, longitude: f64) -> String {
+        // more decimals are refused, and would defeat caching anyway
+        let round = |value: f64| (value * 10_000.).round() / 10_000.;
+        format!(
+            "{}/locationforecast/2.0/complete?lat={}&lon={}",
+            self.base_url,
+            round(latitude),
+            round(longitude)
+        )
+    }
+
+    /// Forecast document for `url`, from the cache until it expires.
+    fn fetch(&self, url: &str) -> ProviderResult<String> {
+        let mut headers = HeaderMap::new();
+        if let Some(user_agent) = &self.user_agent {
+            let value = HeaderValue::from_str(user_agent)
+                .map_err(|e| ProviderError::RequestFailed(format!("invalid user agent: {}", e)))?;
+            headers.insert(USER_AGENT, value);
+        }
+        let fetched = self.cache.get(&self.client, url, headers)?;
+        if !fetched.is_success() {
+            return Err(Self::parse_error(
+                fetched.status,
+                &fetched.headers,
+                fetched.body,
+            ));
+        }
+        if fetched.status == 203 {
+            eprintln!("warning: MET Norway reports this product version as deprecated");
+        }
+        Ok(fetched.body)
+    }
+
+    /// 403 means a missing or generic User-Agent, 422 a location outside the model.
+    fn parse_error(status: u16, headers: &HeaderMap, body: String) -> ProviderError {
+        let failure = HttpFailure::new(status, headers, None, body);
+        match status {
+            422 => ProviderError::NotFound(failure),
+            _ => ProviderError::from_http(failure),
+        }
+    }
+}
+
+impl WeatherProvider for MetNorway {
+    fn get_weather(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+    ) -> ProviderResult<ProvidersReport> {
+        let body = self.fetch(&self.prepare_url(latitude, longitude))?;
+        let mut forecast: MetNorwayForecast = serde_json::from_str(&body)
+            .map_err(|e| ProviderError::ParseError(format!("met norway: {}", e)))?;
+        // timeseries are in UTC, days are cut at UTC midnight
+        forecast
+            .properties
+            .timeseries
+            .retain(|step| step.time.date_naive() == date);
+        if forecast.properties.timeseries.is_empty() {
+            return Err(ProviderError::DateIsOutOfRange("met norway".to_string()));
+        }
+        ProvidersReport::try_from(forecast)
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        Self::CAPABILITIES
+    }
+}
+
+/// Readable text for a `symbol_code` such as `lightrainshowers_day`.
+pub(crate) fn symbol_summary(symbol_code: &str) -> String {
+    let code = symbol_code.split('_').next().unwrap_or(symbol_code);
+    let fixed = match code {
+        "clearsky" => Some("Clear sky"),
+        "fair" => Some("Fair"),
+        "partlycloudy" => Some("Partly cloudy"),
+        "cloudy" => Some("Cloudy"),
+        "fog" => Some("Fog"),
+        _ => None,
+    };
+    if let Some(text) = fixed {
+        return text.to_string();
+    }
+    let (thunder, rest) = match code.strip_suffix("andthunder") {
+        Some(rest) => (true, rest),
+        None => (false, code),
+    };
+    let (showers, rest) = match rest.strip_suffix("showers") {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    // MET spells two light showers codes with a double "s"
+    let (intensity, kind) = if let Some(kind) = rest.strip_prefix("heavy") {
+        (Some("heavy"), kind)
+    } else if let Some(kind) = rest.strip_prefix("light") {
+        let kind = match kind {
+            "ssleet" | "ssnow" => &kind[1..],
+            kind => kind,
+        };
+        (Some("light"), kind)
+    } else {
+        (None, rest)
+    };
+    if !matches!(kind, "rain" | "sleet" | "snow") {
+        return symbol_code.to_string();
+    }
+    let mut words: Vec<&str> = intensity.into_iter().collect();
+    words.push(kind);
+    if showers {
+        words.push("showers");
+    }
+    if thunder {
+        words.push("and thunder");
+    }
+    let text = words.join(" ");
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpSettings;
+
+    fn client() -> HttpClient {
+        HttpClient::new(&HttpSettings::default()).unwrap()
+    }
+
+    #[test]
+    fn test_prepare_url_rounds_coordinates() {
+        let provider = MetNorway::new(None, None, client());
+        assert_eq!(
+            provider.prepare_url(59.913_868_2, 10.752_245),
+            "https://api.met.no/weatherapi/locationforecast/2.0/complete?lat=59.9139&lon=10.7522"
+        );
+    }
+
+    #[test]
+    fn test_symbol_summary() {
+        assert_eq!(symbol_summary("clearsky_day"), "Clear sky");
+        assert_eq!(
+            symbol_summary("partlycloudy_polartwilight"),
+            "Partly cloudy"
+        );
+        assert_eq!(symbol_summary("rain"), "Rain");
+        assert_eq!(
+            symbol_summary("lightrainshowers_night"),
+            "Light rain showers"
+        );
+        assert_eq!(
+            symbol_summary("heavysnowshowersandthunder_day"),
+            "Heavy snow showers and thunder"
+        );
+        assert_eq!(
+            symbol_summary("lightssleetshowersandthunder_day"),
+            "Light sleet showers and thunder"
+        );
+        assert_eq!(symbol_summary("sleetandthunder"), "Sleet and thunder");
+        assert_eq!(symbol_summary("hail"), "hail");
+    }
+
+    #[test]
+    fn test_unprocessable_location_is_not_found() {
+        let err = MetNorway::parse_error(422, &HeaderMap::new(), "no data".to_string());
+        assert!(matches!(err, ProviderError::NotFound(_)));
+    }
+}
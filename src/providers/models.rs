@@ -1,9 +1,62 @@
 use serde::Deserialize;
 pub mod weather_api {
     use super::*;
+    use chrono::{DateTime, FixedOffset, NaiveDate};
+
     #[derive(Deserialize, Debug)]
     pub struct WeatherApiReport {
         pub(crate) forecast: WeatherApiForecast,
+        /// Only with `alerts=yes`, which `forecast.json` and `alerts.json` accept.
+        #[serde(default)]
+        pub(crate) alerts: WeatherApiAlerts,
+    }
+
+    #[derive(Deserialize, Debug, Default)]
+    pub(crate) struct WeatherApiAlerts {
+        #[serde(default)]
+        pub(crate) alert: Vec<WeatherApiAlert>,
+    }
+
+    /// Answer of `current.json` with `aqi=yes`.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct WeatherApiCurrentReport {
+        pub(crate) current: WeatherApiCurrent,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct WeatherApiCurrent {
+        pub(crate) last_updated_epoch: Option<i64>,
+        /// Missing on plans without air quality data.
+        #[serde(default)]
+        pub(crate) air_quality: WeatherApiAirQuality,
+    }
+
+    /// µg/m³, CO included.
+    #[derive(Deserialize, Debug, Default)]
+    pub(crate) struct WeatherApiAirQuality {
+        pub(crate) co: Option<f32>,
+        pub(crate) no2: Option<f32>,
+        pub(crate) o3: Option<f32>,
+        pub(crate) so2: Option<f32>,
+        pub(crate) pm2_5: Option<f32>,
+        pub(crate) pm10: Option<f32>,
+    }
+
+    /// Answer of `alerts.json`.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct WeatherApiAlertsReport {
+        #[serde(default)]
+        pub(crate) alerts: WeatherApiAlerts,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct WeatherApiAlert {
+        pub(crate) headline: Option<String>,
+        pub(crate) severity: Option<String>,
+        pub(crate) event: Option<String>,
+        pub(crate) effective: Option<DateTime<FixedOffset>>,
+        pub(crate) expires: Option<DateTime<FixedOffset>>,
+        pub(crate) desc: Option<String>,
     }
 
     #[derive(Deserialize, Debug)]
@@ -13,7 +66,11 @@ pub mod weather_api {
 
     #[derive(Deserialize, Debug)]
     pub(crate) struct WeatherApiForecastDay {
+        pub(crate) date: NaiveDate,
         pub(crate) day: WeatherApiDay,
+        /// Missing from `future.json`.
+        #[serde(default)]
+        pub(crate) hour: Vec<WeatherApiHour>,
     }
 
     #[derive(Deserialize, Debug)]
@@ -25,15 +82,43 @@ pub mod weather_api {
         pub(crate) avghumidity: f32,
         pub(crate) daily_chance_of_rain: f32,
         pub(crate) condition: WeatherApiCondition,
+        pub(crate) totalprecip_mm: Option<f32>,
+        pub(crate) totalsnow_cm: Option<f32>,
+        pub(crate) avgvis_km: Option<f32>,
+        pub(crate) uv: Option<f32>,
+        /// 1 or 0.
+        pub(crate) daily_will_it_rain: Option<u8>,
+        pub(crate) daily_will_it_snow: Option<u8>,
+    }
+
+    /// Only the fields the day block doesn't aggregate, or only gives the maximum of.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct WeatherApiHour {
+        pub(crate) feelslike_c: Option<f32>,
+        pub(crate) dewpoint_c: Option<f32>,
+        pub(crate) pressure_mb: Option<f32>,
+        /// %.
+        pub(crate) cloud: Option<f32>,
+        pub(crate) wind_kph: Option<f32>,
+        /// Where the wind blows from.
+        pub(crate) wind_degree: Option<f32>,
+        pub(crate) gust_kph: Option<f32>,
     }
 
     #[derive(Deserialize, Debug)]
     pub(crate) struct WeatherApiCondition {
         pub(crate) text: String,
+        pub(crate) code: Option<u32>,
     }
 
     #[derive(Deserialize, Debug)]
     pub(crate) struct WeatherApiForecastError {
+        pub(crate) error: WeatherApiErrorDetail,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct WeatherApiErrorDetail {
+        pub(crate) code: i32,
         pub(crate) message: String,
     }
 }
@@ -44,6 +129,27 @@ pub mod openweather_api {
     #[derive(Deserialize, Debug)]
     pub(crate) struct OpenWeatherReport {
         pub(crate) daily: Vec<OpenWeatherDaily>,
+        /// Missing when nothing is in effect.
+        #[serde(default)]
+        pub(crate) alerts: Vec<OpenWeatherAlert>,
+    }
+
+    /// One Call with every section but `alerts` excluded.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherAlerts {
+        #[serde(default)]
+        pub(crate) alerts: Vec<OpenWeatherAlert>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherAlert {
+        pub(crate) sender_name: String,
+        pub(crate) event: String,
+        /// Unix seconds.
+        pub(crate) start: i64,
+        pub(crate) end: i64,
+        #[serde(default)]
+        pub(crate) description: String,
     }
 
     #[derive(Deserialize, Debug)]
@@ -55,6 +161,19 @@ pub mod openweather_api {
         pub(crate) wind_speed: f32,
         pub(crate) wind_deg: i32,
         pub(crate) pop: f32,
+        #[serde(default)]
+        pub(crate) weather: Vec<OpenWeatherCondition>,
+        pub(crate) feels_like: Option<OpenWeatherFeelsLike>,
+        pub(crate) dew_point: Option<f32>,
+        /// hPa.
+        pub(crate) pressure: Option<f32>,
+        /// %.
+        pub(crate) clouds: Option<f32>,
+        pub(crate) uvi: Option<f32>,
+        pub(crate) wind_gust: Option<f32>,
+        /// mm, only on days with rain or snow. Snow is its water equivalent.
+        pub(crate) rain: Option<f32>,
+        pub(crate) snow: Option<f32>,
     }
 
     #[derive(Deserialize, Debug)]
@@ -63,4 +182,540 @@ pub mod openweather_api {
         pub(crate) min: f32,
         pub(crate) max: f32,
     }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherFeelsLike {
+        pub(crate) day: f32,
+    }
+
+    /// `cod` comes as a number or a string depending on the endpoint.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherError {
+        pub(crate) cod: Option<serde_json::Value>,
+        pub(crate) message: String,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherDaySummary {
+        /// UTC offset of the location, `+03:00`.
+        pub(crate) tz: Option<String>,
+        pub(crate) cloud_cover: Option<OpenWeatherAfternoon>,
+        pub(crate) pressure: Option<OpenWeatherAfternoon>,
+        pub(crate) humidity: OpenWeatherAfternoon,
+        pub(crate) precipitation: OpenWeatherPrecipitation,
+        pub(crate) temperature: OpenWeatherDayTemperature,
+        pub(crate) wind: OpenWeatherWind,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherAfternoon {
+        pub(crate) afternoon: f32,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherPrecipitation {
+        pub(crate) total: f32,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherDayTemperature {
+        pub(crate) min: f32,
+        pub(crate) max: f32,
+        pub(crate) morning: f32,
+        pub(crate) afternoon: f32,
+        pub(crate) evening: f32,
+        pub(crate) night: f32,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherWind {
+        pub(crate) max: OpenWeatherWindMax,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherWindMax {
+        pub(crate) speed: f32,
+        pub(crate) direction: f32,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherTimemachine {
+        pub(crate) data: Vec<OpenWeatherTimemachineData>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherTimemachineData {
+        pub(crate) weather: Vec<OpenWeatherCondition>,
+        pub(crate) feels_like: Option<f32>,
+        pub(crate) dew_point: Option<f32>,
+        pub(crate) uvi: Option<f32>,
+        /// m.
+        pub(crate) visibility: Option<f32>,
+        pub(crate) wind_gust: Option<f32>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherCondition {
+        pub(crate) id: Option<u32>,
+        pub(crate) description: String,
+    }
+
+    /// Answer of the Air Pollution API, `list` holds the current hour only.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherAirPollution {
+        pub(crate) list: Vec<OpenWeatherAirSample>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherAirSample {
+        pub(crate) dt: i64,
+        pub(crate) components: OpenWeatherAirComponents,
+    }
+
+    /// µg/m³.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenWeatherAirComponents {
+        pub(crate) co: Option<f32>,
+        pub(crate) no2: Option<f32>,
+        pub(crate) o3: Option<f32>,
+        pub(crate) so2: Option<f32>,
+        pub(crate) pm2_5: Option<f32>,
+        pub(crate) pm10: Option<f32>,
+    }
+
+    /// Historical day assembled from `day_summary` and a midday `timemachine` snapshot.
+    #[derive(Debug)]
+    pub(crate) struct OpenWeatherHistoricalDay {
+        pub(crate) summary: OpenWeatherDaySummary,
+        pub(crate) snapshot: OpenWeatherTimemachine,
+    }
+}
+
+pub mod fixture {
+    use super::*;
+    use crate::providers::providers_report::PrecipitationType;
+    use chrono::NaiveDate;
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct FixtureFile {
+        pub(crate) days: Vec<FixtureDay>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct FixtureDay {
+        pub(crate) date: NaiveDate,
+        #[serde(flatten)]
+        pub(crate) report: FixtureReport,
+    }
+
+    /// Every field may be left out, it then shows as unknown.
+    #[derive(Deserialize, Debug, Default)]
+    pub(crate) struct FixtureReport {
+        pub(crate) min_temp: Option<f32>,
+        pub(crate) max_temp: Option<f32>,
+        pub(crate) avg_temp: Option<f32>,
+        pub(crate) pop: Option<f32>,
+        pub(crate) summary: Option<String>,
+        pub(crate) wind_speed: Option<f32>,
+        pub(crate) humidity: Option<f32>,
+        pub(crate) wind_direction: Option<i32>,
+        pub(crate) feels_like: Option<f32>,
+        pub(crate) dew_point: Option<f32>,
+        pub(crate) pressure: Option<f32>,
+        pub(crate) cloud_cover: Option<f32>,
+        pub(crate) uv_index: Option<f32>,
+        pub(crate) visibility: Option<f32>,
+        pub(crate) wind_gust: Option<f32>,
+        pub(crate) precipitation: Option<f32>,
+        /// cm of fresh snow.
+        pub(crate) snowfall: Option<f32>,
+        /// mm of melted snow.
+        pub(crate) snow_water: Option<f32>,
+        pub(crate) precipitation_type: Option<PrecipitationType>,
+    }
+}
+
+pub mod plugin {
+    use super::fixture::FixtureReport;
+    use super::*;
+    use chrono::NaiveDate;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    /// Document written to a plugin's stdin.
+    #[derive(Serialize, Debug)]
+    pub(crate) struct PluginRequest<'a> {
+        pub(crate) protocol: u32,
+        pub(crate) request: &'static str,
+        pub(crate) latitude: f64,
+        pub(crate) longitude: f64,
+        pub(crate) date: NaiveDate,
+        pub(crate) options: &'a BTreeMap<String, serde_json::Value>,
+    }
+
+    /// Document a plugin prints to stdout, a report or an error.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct PluginResponse {
+        pub(crate) protocol: u32,
+        /// Same fields as a fixture day.
+        pub(crate) report: Option<FixtureReport>,
+        pub(crate) error: Option<PluginError>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct PluginError {
+        pub(crate) kind: String,
+        pub(crate) message: String,
+        pub(crate) retry_after: Option<u64>,
+    }
+}
+
+pub mod json_api {
+    use crate::providers::json_api::JsonUnits;
+
+    /// Values a JSON API spec points at in the selected day, in the spec's units.
+    #[derive(Debug)]
+    pub(crate) struct JsonApiDay {
+        pub(crate) min_temp: f32,
+        pub(crate) max_temp: f32,
+        pub(crate) avg_temp: Option<f32>,
+        pub(crate) pop: f32,
+        pub(crate) summary: Option<String>,
+        pub(crate) wind_speed: f32,
+        pub(crate) humidity: f32,
+        pub(crate) wind_direction: Option<f32>,
+        pub(crate) units: JsonUnits,
+    }
+}
+
+pub mod met_norway {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    /// Locationforecast 2.0 `complete` answer, a GeoJSON feature.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct MetNorwayForecast {
+        pub(crate) properties: MetNorwayProperties,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct MetNorwayProperties {
+        pub(crate) timeseries: Vec<MetNorwayStep>,
+    }
+
+    /// Hourly for the first days, 6-hourly after.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct MetNorwayStep {
+        pub(crate) time: DateTime<Utc>,
+        pub(crate) data: MetNorwayStepData,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct MetNorwayStepData {
+        pub(crate) instant: MetNorwayInstant,
+        pub(crate) next_1_hours: Option<MetNorwayPeriod>,
+        pub(crate) next_6_hours: Option<MetNorwayPeriod>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct MetNorwayInstant {
+        pub(crate) details: MetNorwayInstantDetails,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct MetNorwayInstantDetails {
+        pub(crate) air_temperature: f32,
+        pub(crate) relative_humidity: f32,
+        pub(crate) wind_speed: f32,
+        pub(crate) wind_from_direction: Option<f32>,
+        /// hPa.
+        pub(crate) air_pressure_at_sea_level: Option<f32>,
+        /// %.
+        pub(crate) cloud_area_fraction: Option<f32>,
+        pub(crate) dew_point_temperature: Option<f32>,
+        pub(crate) ultraviolet_index_clear_sky: Option<f32>,
+        pub(crate) wind_speed_of_gust: Option<f32>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct MetNorwayPeriod {
+        pub(crate) summary: Option<MetNorwaySummary>,
+        #[serde(default)]
+        pub(crate) details: MetNorwayPeriodDetails,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct MetNorwaySummary {
+        pub(crate) symbol_code: String,
+    }
+
+    #[derive(Deserialize, Debug, Default)]
+    pub(crate) struct MetNorwayPeriodDetails {
+        pub(crate) precipitation_amount: Option<f32>,
+        /// Percent, only in the `complete` variant.
+        pub(crate) probability_of_precipitation: Option<f32>,
+    }
+}
+
+pub mod nws {
+    use super::*;
+    use crate::providers::units::{SpeedUnit, TemperatureUnit};
+    use chrono::{DateTime, FixedOffset};
+
+    /// `/points/{lat},{lon}`, the forecast office grid cell of a location.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct NwsPoint {
+        pub(crate) properties: NwsPointProperties,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub(crate) struct NwsPointProperties {
+        pub(crate) grid_id: String,
+        pub(crate) grid_x: u32,
+        pub(crate) grid_y: u32,
+    }
+
+    /// `/gridpoints/{office}/{x},{y}/forecast`, 12-hour day and night periods.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct NwsForecast {
+        pub(crate) properties: NwsForecastProperties,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct NwsForecastProperties {
+        pub(crate) periods: Vec<NwsPeriod>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub(crate) struct NwsPeriod {
+        /// In the office's local time.
+        pub(crate) start_time: DateTime<FixedOffset>,
+        pub(crate) is_daytime: bool,
+        pub(crate) temperature: f32,
+        /// `C` with `units=si`, `F` otherwise.
+        pub(crate) temperature_unit: String,
+        pub(crate) probability_of_precipitation: Option<NwsValue>,
+        pub(crate) short_forecast: String,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct NwsValue {
+        pub(crate) value: Option<f32>,
+    }
+
+    /// `/gridpoints/{office}/{x},{y}`, raw forecast layers.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct NwsGridData {
+        pub(crate) properties: NwsGridProperties,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub(crate) struct NwsGridProperties {
+        pub(crate) temperature: NwsLayer,
+        pub(crate) relative_humidity: NwsLayer,
+        pub(crate) wind_speed: NwsLayer,
+        pub(crate) wind_direction: NwsLayer,
+        #[serde(default)]
+        pub(crate) apparent_temperature: NwsLayer,
+        #[serde(default)]
+        pub(crate) dewpoint: NwsLayer,
+        #[serde(default)]
+        pub(crate) sky_cover: NwsLayer,
+        #[serde(default)]
+        pub(crate) wind_gust: NwsLayer,
+        /// m.
+        #[serde(default)]
+        pub(crate) visibility: NwsLayer,
+        /// Amount per interval, mm.
+        #[serde(default)]
+        pub(crate) quantitative_precipitation: NwsLayer,
+        #[serde(default)]
+        pub(crate) snowfall_amount: NwsLayer,
+    }
+
+    #[derive(Deserialize, Debug, Default)]
+    pub(crate) struct NwsLayer {
+        /// e.g. `wmoUnit:degC`, `wmoUnit:km_h-1`.
+        pub(crate) uom: Option<String>,
+        pub(crate) values: Vec<NwsLayerValue>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub(crate) struct NwsLayerValue {
+        /// ISO 8601 interval, `2025-07-01T10:00:00+00:00/PT3H`.
+        pub(crate) valid_time: String,
+        pub(crate) value: Option<f32>,
+    }
+
+    /// Hourly samples of the grid layers over one local day, in the day's units.
+    #[derive(Debug, Default)]
+    pub(crate) struct NwsHours {
+        pub(crate) temperature: Vec<f32>,
+        pub(crate) humidity: Vec<f32>,
+        pub(crate) wind_speed: Vec<f32>,
+        pub(crate) wind_direction: Vec<f32>,
+        pub(crate) apparent_temperature: Vec<f32>,
+        pub(crate) dew_point: Vec<f32>,
+        pub(crate) sky_cover: Vec<f32>,
+        pub(crate) wind_gust: Vec<f32>,
+        /// m.
+        pub(crate) visibility: Vec<f32>,
+        /// Totals over the day, mm; snow as its fallen depth.
+        pub(crate) precipitation: Option<f32>,
+        pub(crate) snow: Option<f32>,
+    }
+
+    /// Everything known about one local day: its forecast periods and grid hours.
+    #[derive(Debug)]
+    pub(crate) struct NwsDay {
+        pub(crate) periods: Vec<NwsPeriod>,
+        pub(crate) hours: NwsHours,
+        /// Of the period temperatures and the grid temperature layers.
+        pub(crate) temperature_unit: TemperatureUnit,
+        /// Of the wind speed and gust layers.
+        pub(crate) speed_unit: SpeedUnit,
+    }
+
+    /// `/alerts/active?point=...`.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct NwsAlerts {
+        pub(crate) features: Vec<NwsAlertFeature>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct NwsAlertFeature {
+        pub(crate) properties: NwsAlert,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub(crate) struct NwsAlert {
+        pub(crate) sender_name: String,
+        pub(crate) event: String,
+        /// `Extreme`, `Severe`, `Moderate`, `Minor` or `Unknown`.
+        pub(crate) severity: String,
+        pub(crate) headline: Option<String>,
+        pub(crate) onset: Option<DateTime<FixedOffset>>,
+        pub(crate) ends: Option<DateTime<FixedOffset>>,
+        pub(crate) expires: Option<DateTime<FixedOffset>>,
+        #[serde(default)]
+        pub(crate) description: String,
+    }
+
+    /// RFC 7807 problem document sent with every error.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct NwsProblem {
+        #[serde(rename = "type")]
+        pub(crate) kind: Option<String>,
+        pub(crate) title: Option<String>,
+        pub(crate) detail: Option<String>,
+    }
+}
+
+pub mod visual_crossing {
+    use super::*;
+    use chrono::NaiveDate;
+
+    /// Timeline API answer with `unitGroup=metric`.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct VisualCrossingTimeline {
+        pub(crate) days: Vec<VisualCrossingDay>,
+        /// Only with `include=alerts`.
+        #[serde(default)]
+        pub(crate) alerts: Vec<VisualCrossingAlert>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub(crate) struct VisualCrossingAlert {
+        pub(crate) event: String,
+        pub(crate) headline: Option<String>,
+        pub(crate) description: Option<String>,
+        pub(crate) onset_epoch: Option<i64>,
+        pub(crate) ends_epoch: Option<i64>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct VisualCrossingDay {
+        pub(crate) datetime: NaiveDate,
+        pub(crate) tempmax: f32,
+        pub(crate) tempmin: f32,
+        pub(crate) temp: f32,
+        pub(crate) humidity: f32,
+        /// Percent, null for observed days.
+        pub(crate) precipprob: Option<f32>,
+        /// mm.
+        pub(crate) precip: Option<f32>,
+        /// km/h.
+        pub(crate) windspeed: f32,
+        pub(crate) winddir: Option<f32>,
+        pub(crate) conditions: String,
+        pub(crate) feelslike: Option<f32>,
+        pub(crate) dew: Option<f32>,
+        /// hPa.
+        pub(crate) pressure: Option<f32>,
+        /// %.
+        pub(crate) cloudcover: Option<f32>,
+        pub(crate) uvindex: Option<f32>,
+        /// km.
+        pub(crate) visibility: Option<f32>,
+        /// km/h.
+        pub(crate) windgust: Option<f32>,
+        /// Fallen depth, cm.
+        pub(crate) snow: Option<f32>,
+        /// `rain`, `snow`, `freezingrain` or `ice`, null on dry days.
+        pub(crate) preciptype: Option<Vec<String>>,
+        /// Only with `include=stats`.
+        pub(crate) normal: Option<VisualCrossingNormals>,
+    }
+
+    /// `[min, mean, max]` over the reference period, each value may be null.
+    pub(crate) type VisualCrossingNormal = Option<[Option<f32>; 3]>;
+
+    #[derive(Deserialize, Debug, Default)]
+    #[serde(default)]
+    pub(crate) struct VisualCrossingNormals {
+        pub(crate) tempmax: VisualCrossingNormal,
+        pub(crate) tempmin: VisualCrossingNormal,
+        pub(crate) precip: VisualCrossingNormal,
+        pub(crate) humidity: VisualCrossingNormal,
+        pub(crate) windspeed: VisualCrossingNormal,
+    }
+}
+
+pub mod open_meteo {
+    use super::*;
+
+    /// Air quality API answer with `timeformat=unixtime`.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenMeteoAirQuality {
+        pub(crate) current: OpenMeteoAirCurrent,
+    }
+
+    /// µg/m³ for pollutants, grains/m³ for pollen, which is null outside Europe.
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenMeteoAirCurrent {
+        pub(crate) time: i64,
+        pub(crate) pm10: Option<f32>,
+        pub(crate) pm2_5: Option<f32>,
+        pub(crate) carbon_monoxide: Option<f32>,
+        pub(crate) nitrogen_dioxide: Option<f32>,
+        pub(crate) sulphur_dioxide: Option<f32>,
+        pub(crate) ozone: Option<f32>,
+        pub(crate) alder_pollen: Option<f32>,
+        pub(crate) birch_pollen: Option<f32>,
+        pub(crate) grass_pollen: Option<f32>,
+        pub(crate) mugwort_pollen: Option<f32>,
+        pub(crate) olive_pollen: Option<f32>,
+        pub(crate) ragweed_pollen: Option<f32>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub(crate) struct OpenMeteoError {
+        pub(crate) reason: String,
+    }
 }
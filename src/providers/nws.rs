@@ -0,0 +1,359 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Utc};
+use reqwest::header::{ACCEPT, HeaderMap, HeaderValue};
+use serde::de::DeserializeOwned;
+
+use super::ProviderCapabilities;
+use super::alerts::WeatherAlert;
+use super::error::{HttpFailure, ProviderError, ProviderResult};
+use super::models::nws::{
+    NwsAlerts, NwsDay, NwsForecast, NwsGridData, NwsHours, NwsLayer, NwsPoint, NwsProblem,
+};
+use super::provider_trait::WeatherProvider;
+use super::providers_report::ProvidersReport;
+use super::units::{SpeedUnit, TemperatureUnit};
+use crate::http::HttpClient;
+use crate::http_cache::ResponseCache;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.weather.gov";
+/// A location's grid cell only moves when an office redraws its grid.
+const GRID_CACHE_DAYS: i64 = 30;
+
+/// US National Weather Service, keyless and US territories only.
+///
+/// A location is looked up once through `/points` to its forecast office grid cell,
+/// then the cell's 12-hour forecast periods and raw hourly layers are combined.
+pub struct Nws {
+    base_url: String,
+    client: HttpClient,
+    cache: ResponseCache,
+}
+
+impl Nws {
+    /// Seven days of day and night periods starting today, no history.
+    pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 6,
+        history_days: Some(0),
+        hourly: true,
+        alerts: true,
+        air_quality: false,
+        requires_key: false,
+    };
+
+    pub fn new(base_url: Option<String>, client: HttpClient) -> Self {
+        let base_url = base_url.unwrap_or(DEFAULT_BASE_URL.to_string());
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+            cache: ResponseCache::default(),
+        }
+    }
+
+    /// Same provider keeping its answers in `cache`.
+    pub fn with_cache(self, cache: ResponseCache) -> Self {
+        Self { cache, ..self }
+    }
+
+    /// `lat,lon` with the 4 decimals the API accepts, more are redirected.
+    fn point(latitude: f64, longitude: f64) -> String {
+        let round = |value: f64| (value * 10_000.).round() / 10_000.;
+        format!("{},{}", round(latitude), round(longitude))
+    }
+
+    /// GETs a geo+json document through the cache.
+    fn fetch<T: DeserializeOwned>(&self, url: &str) -> ProviderResult<T> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/geo+json"));
+        let fetched = self.cache.get(&self.client, url, headers)?;
+        if !fetched.is_success() {
+            return Err(Self::parse_error(
+                fetched.status,
+                &fetched.headers,
+                &fetched.body,
+            ));
+        }
+        serde_json::from_str(&fetched.body)
+            .map_err(|e| ProviderError::ParseError(format!("nws: {}", e)))
+    }
+
+    /// Grid cell URL of a location, `{base}/gridpoints/{office}/{x},{y}`.
+    fn gridpoint_url(&self, latitude: f64, longitude: f64) -> ProviderResult<String> {
+        let url = format!(
+            "{}/points/{}",
+            self.base_url,
+            Self::point(latitude, longitude)
+        );
+        let point: NwsPoint = self.fetch(&url)?;
+        self.cache
+            .keep_until(&url, Utc::now() + Duration::days(GRID_CACHE_DAYS));
+        let grid = point.properties;
+        Ok(format!(
+            "{}/gridpoints/{}/{},{}",
+            self.base_url, grid.grid_id, grid.grid_x, grid.grid_y
+        ))
+    }
+
+    /// Errors come as problem documents, `InvalidPoint` for locations outside the US.
+    fn parse_error(status: u16, headers: &HeaderMap, body: &str) -> ProviderError {
+        let (code, message) = match serde_json::from_str::<NwsProblem>(body) {
+            Ok(problem) => (
+                problem
+                    .kind
+                    .as_deref()
+                    .and_then(|kind| kind.rsplit('/').next())
+                    .map(str::to_string),
+                problem.detail.or(problem.title).unwrap_or_default(),
+            ),
+            Err(_) => (None, body.to_string()),
+        };
+        ProviderError::from_http(HttpFailure::new(status, headers, code, message))
+    }
+}
+
+impl WeatherProvider for Nws {
+    fn get_weather(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+    ) -> ProviderResult<ProvidersReport> {
+        let gridpoint = self.gridpoint_url(latitude, longitude)?;
+        let forecast: NwsForecast = self.fetch(&format!("{}/forecast?units=si", gridpoint))?;
+        let periods: Vec<_> = forecast
+            .properties
+            .periods
+            .into_iter()
+            .filter(|period| period.start_time.date_naive() == date)
+            .collect();
+        let Some(offset) = periods.first().map(|period| *period.start_time.offset()) else {
+            return Err(ProviderError::DateIsOutOfRange("nws".to_string()));
+        };
+        let temperature_unit = match periods[0].temperature_unit.as_str() {
+            "F" => TemperatureUnit::Fahrenheit,
+            _ => TemperatureUnit::Celsius,
+        };
+        let grid: NwsGridData = self.fetch(&gridpoint)?;
+        let layers = grid.properties;
+        let speed_unit = layer_speed_unit(&layers.wind_speed);
+        // normalize converts the whole day from one unit, a layer in another is left out
+        let temperatures = |layer: &NwsLayer| match layer_temperature_unit(layer) {
+            unit if unit == temperature_unit => hourly(layer, date, offset),
+            _ => Vec::new(),
+        };
+        let speeds = |layer: &NwsLayer| match layer_speed_unit(layer) {
+            unit if unit == speed_unit => hourly(layer, date, offset),
+            _ => Vec::new(),
+        };
+        let hours = NwsHours {
+            temperature: temperatures(&layers.temperature),
+            humidity: hourly(&layers.relative_humidity, date, offset),
+            wind_speed: hourly(&layers.wind_speed, date, offset),
+            wind_direction: hourly(&layers.wind_direction, date, offset),
+            apparent_temperature: temperatures(&layers.apparent_temperature),
+            dew_point: temperatures(&layers.dewpoint),
+            sky_cover: hourly(&layers.sky_cover, date, offset),
+            wind_gust: speeds(&layers.wind_gust),
+            visibility: hourly(&layers.visibility, date, offset),
+            precipitation: daily_total(&layers.quantitative_precipitation, date, offset),
+            snow: daily_total(&layers.snowfall_amount, date, offset),
+        };
+        // a failing alerts endpoint shouldn't cost the forecast
+        let mut alerts = self.get_alerts(latitude, longitude).unwrap_or_else(|err| {
+            eprintln!(
+                "warning: NWS alerts unavailable ({}), shown without them",
+                err
+            );
+            Vec::new()
+        });
+        alerts.retain(|alert| alert.covers(date));
+        let day = NwsDay {
+            periods,
+            hours,
+            temperature_unit,
+            speed_unit,
+        };
+        Ok(ProvidersReport::try_from(day)?.with_alerts(alerts))
+    }
+
+    /// Alerts in effect at a location, in the order the API lists them.
+    fn get_alerts(&self, latitude: f64, longitude: f64) -> ProviderResult<Vec<WeatherAlert>> {
+        let url = format!(
+            "{}/alerts/active?point={}",
+            self.base_url,
+            Self::point(latitude, longitude)
+        );
+        let alerts: NwsAlerts = self.fetch(&url)?;
+        Ok(alerts
+            .features
+            .into_iter()
+            .map(|feature| WeatherAlert::from(feature.properties))
+            .collect())
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        Self::CAPABILITIES
+    }
+}
+
+/// Values of `layer` repeated for every hour they cover inside `date`, local to `offset`.
+fn hourly(layer: &NwsLayer, date: NaiveDate, offset: FixedOffset) -> Vec<f32> {
+    let mut samples = Vec::new();
+    for entry in &layer.values {
+        let Some(value) = entry.value else { continue };
+        let Some((start, duration)) = entry.valid_time.split_once('/') else {
+            continue;
+        };
+        let (Ok(start), Some(hours)) = (
+            DateTime::parse_from_rfc3339(start),
+            duration_hours(duration),
+        ) else {
+            continue;
+        };
+        for hour in 0..hours {
+            let time = (start + Duration::hours(hour)).with_timezone(&offset);
+            if time.date_naive() == date {
+                samples.push(value);
+            }
+        }
+    }
+    samples
+}
+
+/// Share of each amount in `layer` that falls inside `date`, assuming an even rate over
+/// its interval. `None` when no interval overlaps the day.
+fn daily_total(layer: &NwsLayer, date: NaiveDate, offset: FixedOffset) -> Option<f32> {
+    let mut total = None;
+    for entry in &layer.values {
+        let Some(value) = entry.value else { continue };
+        let Some((start, duration)) = entry.valid_time.split_once('/') else {
+            continue;
+        };
+        let (Ok(start), Some(hours)) = (
+            DateTime::parse_from_rfc3339(start),
+            duration_hours(duration),
+        ) else {
+            continue;
+        };
+        let inside = (0..hours)
+            .filter(|hour| {
+                (start + Duration::hours(*hour))
+                    .with_timezone(&offset)
+                    .date_naive()
+                    == date
+            })
+            .count();
+        if inside > 0 {
+            *total.get_or_insert(0.) += value * inside as f32 / hours as f32;
+        }
+    }
+    total
+}
+
+/// Unit of a speed layer, which comes in km/h unless stated otherwise.
+fn layer_speed_unit(layer: &NwsLayer) -> SpeedUnit {
+    match layer.uom.as_deref() {
+        Some(uom) if uom.ends_with(":m_s-1") => SpeedUnit::Mps,
+        _ => SpeedUnit::Kph,
+    }
+}
+
+/// Unit of a temperature layer, which comes in °C unless stated otherwise.
+fn layer_temperature_unit(layer: &NwsLayer) -> TemperatureUnit {
+    match layer.uom.as_deref() {
+        Some(uom) if uom.ends_with(":degF") => TemperatureUnit::Fahrenheit,
+        _ => TemperatureUnit::Celsius,
+    }
+}
+
+/// Whole hours of an ISO 8601 duration such as `PT3H` or `P1DT6H`, at least one.
+fn duration_hours(duration: &str) -> Option<i64> {
+    let rest = duration.strip_prefix('P')?;
+    let (days, time) = match rest.split_once('T') {
+        Some((days, time)) => (days, time),
+        None => (rest, ""),
+    };
+    let days = match days.strip_suffix('D') {
+        Some(days) => days.parse::<i64>().ok()?,
+        None if days.is_empty() => 0,
+        None => return None,
+    };
+    let mut hours = days * 24;
+    let mut number = String::new();
+    for c in time.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'H' => hours += std::mem::take(&mut number).parse::<i64>().ok()?,
+            'M' | 'S' => number.clear(),
+            _ => return None,
+        }
+    }
+    Some(hours.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::models::nws::NwsLayerValue;
+
+    fn layer(values: &[(&str, f32)]) -> NwsLayer {
+        NwsLayer {
+            uom: None,
+            values: values
+                .iter()
+                .map(|(valid_time, value)| NwsLayerValue {
+                    valid_time: valid_time.to_string(),
+                    value: Some(*value),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_duration_hours() {
+        assert_eq!(duration_hours("PT1H"), Some(1));
+        assert_eq!(duration_hours("PT13H"), Some(13));
+        assert_eq!(duration_hours("P1D"), Some(24));
+        assert_eq!(duration_hours("P2DT6H"), Some(54));
+        assert_eq!(duration_hours("PT30M"), Some(1));
+        assert_eq!(duration_hours("3H"), None);
+    }
+
+    #[test]
+    fn test_hourly_cuts_local_day() {
+        let eastern = FixedOffset::west_opt(4 * 3600).unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        // New York's July 1 runs from 04:00 UTC to 04:00 UTC the next day
+        let layer = layer(&[
+            ("2025-07-01T02:00:00+00:00/PT3H", 20.),
+            ("2025-07-01T05:00:00+00:00/PT2H", 18.),
+            ("2025-07-02T03:00:00+00:00/PT2H", 25.),
+        ]);
+        assert_eq!(hourly(&layer, date, eastern), vec![20., 18., 18., 25.]);
+    }
+
+    #[test]
+    fn test_daily_total_splits_amounts() {
+        let eastern = FixedOffset::west_opt(4 * 3600).unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 7, 1).unwrap();
+        // 3 mm over 03:00-09:00 UTC, of which 04:00-09:00 is July 1 in New York
+        let rain = layer(&[
+            ("2025-07-01T03:00:00+00:00/PT6H", 3.),
+            ("2025-07-02T06:00:00+00:00/PT6H", 4.),
+        ]);
+        assert_eq!(daily_total(&rain, date, eastern), Some(2.5));
+        assert_eq!(daily_total(&layer(&[]), date, eastern), None);
+    }
+
+    #[test]
+    fn test_invalid_point_is_not_found() {
+        let body = r#"{"type":"https://api.weather.gov/problems/InvalidPoint",
+            "title":"Invalid Point","status":404,"detail":"Unable to provide data for requested point 50.45,30.52"}"#;
+        let err = Nws::parse_error(404, &HeaderMap::new(), body);
+        match err {
+            ProviderError::NotFound(failure) => {
+                assert_eq!(failure.code.as_deref(), Some("InvalidPoint"));
+                assert!(failure.message.contains("requested point"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+
+use super::air_quality::AirQuality;
+use super::error::{HttpFailure, ProviderError, ProviderResult};
+use super::models::open_meteo::{OpenMeteoAirQuality, OpenMeteoError};
+use crate::http::HttpClient;
+
+pub const DEFAULT_AIR_QUALITY_URL: &str = "https://air-quality-api.open-meteo.com/v1";
+
+const CURRENT: &str = "pm10,pm2_5,carbon_monoxide,nitrogen_dioxide,sulphur_dioxide,ozone,\
+    alder_pollen,birch_pollen,grass_pollen,mugwort_pollen,olive_pollen,ragweed_pollen";
+
+/// Open-Meteo air quality API, keyless, with pollen for Europe.
+/// Serves `weather air` when the selected provider has no air quality data.
+pub struct OpenMeteoAir {
+    base_url: String,
+    client: HttpClient,
+}
+
+impl OpenMeteoAir {
+    pub fn new(base_url: Option<String>, client: HttpClient) -> Self {
+        let base_url = base_url.unwrap_or(DEFAULT_AIR_QUALITY_URL.to_string());
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    fn prepare_url(&self, latitude: f64, longitude: f64) -> String {
+        format!(
+            "{}/air-quality?latitude={}&longitude={}&current={}&timeformat=unixtime",
+            self.base_url, latitude, longitude, CURRENT
+        )
+    }
+
+    /// Errors come as `{"error": true, "reason": "..."}`.
+    fn parse_error(status: u16, headers: &HeaderMap, body: &str) -> ProviderError {
+        let message = serde_json::from_str::<OpenMeteoError>(body)
+            .map(|err| err.reason)
+            .unwrap_or(body.to_string());
+        ProviderError::from_http(HttpFailure::new(status, headers, None, message))
+    }
+
+    pub fn get_air_quality(&self, latitude: f64, longitude: f64) -> ProviderResult<AirQuality> {
+        let response = self.client.get(&self.prepare_url(latitude, longitude))?;
+        match response.status() {
+            StatusCode::OK => {
+                let body: OpenMeteoAirQuality = response
+                    .json()
+                    .map_err(|err| ProviderError::ParseError(err.without_url().to_string()))?;
+                Ok(AirQuality::from(body.current))
+            }
+            status => {
+                let headers = response.headers().clone();
+                let body = response.text().unwrap_or_default();
+                Err(Self::parse_error(status.as_u16(), &headers, &body))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpSettings;
+
+    #[test]
+    fn test_prepare_url() {
+        let client = HttpClient::new(&HttpSettings::default()).unwrap();
+        let url = OpenMeteoAir::new(None, client).prepare_url(52.52, 13.41);
+        assert!(url.starts_with(
+            "https://air-quality-api.open-meteo.com/v1/air-quality?latitude=52.52&longitude=13.41&current=pm10,pm2_5,"
+        ));
+        assert!(url.ends_with("ragweed_pollen&timeformat=unixtime"));
+    }
+
+    #[test]
+    fn test_parse_error_reason() {
+        let body =
+            r#"{"error":true,"reason":"Latitude must be in range of -90 to 90°. Given: 91.0."}"#;
+        let err = OpenMeteoAir::parse_error(400, &HeaderMap::new(), body);
+        assert!(matches!(
+            err,
+            ProviderError::ProviderMsgError(ref failure) if failure.message.starts_with("Latitude")
+        ));
+    }
+}
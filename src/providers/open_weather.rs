@@ -1,27 +1,159 @@
-use chrono::{DateTime, NaiveDate};
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate};
 use reqwest::StatusCode;
-use reqwest::blocking::get;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
 
-use super::error::ProviderError;
-use super::models::openweather_api::OpenWeatherReport;
+use super::air_quality::AirQuality;
+use super::alerts::WeatherAlert;
+use super::error::{HttpFailure, ProviderError};
+use super::models::openweather_api::{
+    OpenWeatherAirPollution, OpenWeatherAlerts, OpenWeatherDaySummary, OpenWeatherError,
+    OpenWeatherHistoricalDay, OpenWeatherReport, OpenWeatherTimemachine,
+};
 use super::provider_trait::WeatherProvider;
-use super::{ProviderResult, providers_report::ProvidersReport};
+use super::{ProviderCapabilities, ProviderResult, providers_report::ProvidersReport};
+use crate::http::HttpClient;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.openweathermap.org/data/3.0";
 
 pub struct OpenWeather {
     api_key: String,
+    base_url: String,
+    client: HttpClient,
 }
 
 impl OpenWeather {
-    pub fn new(api_key: String) -> Self {
-        Self { api_key }
+    /// One Call 3.0 `daily` holds today plus 7 days, `day_summary` reaches back to 1979-01-02.
+    pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 7,
+        history_days: Some(45 * 365),
+        hourly: true,
+        alerts: true,
+        air_quality: true,
+        requires_key: true,
+    };
+
+    pub fn new(api_key: String, base_url: Option<String>, client: HttpClient) -> Self {
+        let base_url = base_url.unwrap_or(DEFAULT_BASE_URL.to_string());
+        Self {
+            api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }
     }
 
     fn prepare_url(&self, lat: f64, lon: f64) -> String {
         format!(
-            "https://api.openweathermap.org/data/3.0/onecall?lat={}&lon={}&appid={}&exclude=hourly,minutely&units=metric",
-            lat, lon, self.api_key
+            "{}/onecall?lat={}&lon={}&appid={}&exclude=hourly,minutely&units=metric",
+            self.base_url, lat, lon, self.api_key
+        )
+    }
+
+    fn prepare_alerts_url(&self, lat: f64, lon: f64) -> String {
+        format!(
+            "{}/onecall?lat={}&lon={}&appid={}&exclude=current,minutely,hourly,daily",
+            self.base_url, lat, lon, self.api_key
+        )
+    }
+
+    /// The Air Pollution API lives under `data/2.5`, next to One Call's `data/3.0`;
+    /// other base URLs are used as they are.
+    fn prepare_air_pollution_url(&self, lat: f64, lon: f64) -> String {
+        let base_url = self
+            .base_url
+            .strip_suffix("/3.0")
+            .map(|base| format!("{base}/2.5"))
+            .unwrap_or(self.base_url.clone());
+        format!(
+            "{}/air_pollution?lat={}&lon={}&appid={}",
+            base_url, lat, lon, self.api_key
+        )
+    }
+
+    fn prepare_day_summary_url(&self, lat: f64, lon: f64, date: NaiveDate) -> String {
+        format!(
+            "{}/onecall/day_summary?lat={}&lon={}&date={}&appid={}&units=metric",
+            self.base_url, lat, lon, date, self.api_key
+        )
+    }
+
+    fn prepare_timemachine_url(&self, lat: f64, lon: f64, dt: i64) -> String {
+        format!(
+            "{}/onecall/timemachine?lat={}&lon={}&dt={}&appid={}&units=metric",
+            self.base_url, lat, lon, dt, self.api_key
         )
     }
+
+    fn fetch<T: DeserializeOwned>(&self, url: String) -> ProviderResult<T> {
+        let response = self.client.get(&url)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .map_err(|e| ProviderError::ParseError(e.without_url().to_string())),
+            status => {
+                let headers = response.headers().clone();
+                let body = response
+                    .text()
+                    .unwrap_or("failed to extract text".to_string());
+                Err(Self::parse_error(status.as_u16(), &headers, &body))
+            }
+        }
+    }
+
+    fn parse_error(status: u16, headers: &HeaderMap, body: &str) -> ProviderError {
+        let failure = match serde_json::from_str::<OpenWeatherError>(body) {
+            Ok(err) => {
+                let code = err.cod.map(|cod| match cod {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                });
+                HttpFailure::new(status, headers, code, err.message)
+            }
+            Err(_) => HttpFailure::new(status, headers, None, body.to_string()),
+        };
+        ProviderError::from_http(failure)
+    }
+
+    fn get_forecast(&self, lat: f64, lon: f64, date: NaiveDate) -> ProviderResult<ProvidersReport> {
+        let resp_body: OpenWeatherReport = self.fetch(self.prepare_url(lat, lon))?;
+        let day = resp_body
+            .daily
+            .into_iter()
+            .find(|el| {
+                let dt = DateTime::from_timestamp(el.dt, 0).unwrap_or_default();
+                let naive = dt.naive_local();
+                date == naive.date()
+            })
+            .ok_or(ProviderError::DateIsOutOfRange("open weather".to_string()))?;
+        let alerts = resp_body
+            .alerts
+            .into_iter()
+            .map(WeatherAlert::from)
+            .filter(|alert| alert.covers(date))
+            .collect();
+        Ok(ProvidersReport::try_from(day)?.with_alerts(alerts))
+    }
+
+    fn get_history(&self, lat: f64, lon: f64, date: NaiveDate) -> ProviderResult<ProvidersReport> {
+        let summary: OpenWeatherDaySummary =
+            self.fetch(self.prepare_day_summary_url(lat, lon, date))?;
+        // day_summary carries no description, take it from a snapshot at local noon
+        let midday = local_noon(date, summary.tz.as_deref(), lon);
+        let snapshot: OpenWeatherTimemachine =
+            self.fetch(self.prepare_timemachine_url(lat, lon, midday))?;
+        ProvidersReport::try_from(OpenWeatherHistoricalDay { summary, snapshot })
+    }
+}
+
+/// Unix time of noon on `date` at the UTC offset `tz`, or at the solar time of `lon`
+/// when the offset is missing.
+fn local_noon(date: NaiveDate, tz: Option<&str>, lon: f64) -> i64 {
+    let offset = tz
+        .and_then(|tz| tz.parse::<FixedOffset>().ok())
+        .map(|offset| offset.local_minus_utc())
+        .unwrap_or((lon * 240.) as i32);
+    let noon = date.and_hms_opt(12, 0, 0).unwrap_or_default().and_utc();
+    (noon - Duration::seconds(offset.into())).timestamp()
 }
 
 impl WeatherProvider for OpenWeather {
@@ -31,44 +163,124 @@ impl WeatherProvider for OpenWeather {
         longitude: f64,
         date: NaiveDate,
     ) -> ProviderResult<ProvidersReport> {
-        let url = self.prepare_url(latitude, longitude);
-        let response = get(url).map_err(|e| ProviderError::RequestFailed(e.to_string()))?;
-        let status = response.status();
-
-        match status {
-            StatusCode::OK => {
-                let resp_body: OpenWeatherReport = response
-                    .json()
-                    .map_err(|e| ProviderError::ParseError(e.to_string()))?;
-                let day = resp_body
-                    .daily
-                    .into_iter()
-                    .find(|el| {
-                        let dt = DateTime::from_timestamp(el.dt, 0).unwrap_or_default();
-                        let naive = dt.naive_local();
-                        date == naive.date()
-                    })
-                    .ok_or(ProviderError::DateIsOutOfRange("open weather".to_string()))?;
-                ProvidersReport::try_from(day)
-            }
-            _ => Err(ProviderError::ProviderMsgError(
-                response
-                    .text()
-                    .unwrap_or("failed to extract text".to_string()),
-            )),
+        if date < Local::now().naive_local().date() {
+            self.get_history(latitude, longitude, date)
+        } else {
+            self.get_forecast(latitude, longitude, date)
         }
     }
+
+    fn get_alerts(&self, latitude: f64, longitude: f64) -> ProviderResult<Vec<WeatherAlert>> {
+        let body: OpenWeatherAlerts = self.fetch(self.prepare_alerts_url(latitude, longitude))?;
+        Ok(body.alerts.into_iter().map(WeatherAlert::from).collect())
+    }
+
+    fn get_air_quality(&self, latitude: f64, longitude: f64) -> ProviderResult<AirQuality> {
+        let body: OpenWeatherAirPollution =
+            self.fetch(self.prepare_air_pollution_url(latitude, longitude))?;
+        body.list
+            .into_iter()
+            .next()
+            .map(AirQuality::from)
+            .ok_or(ProviderError::ParseError(
+                "no air pollution sample".to_string(),
+            ))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        Self::CAPABILITIES
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http::HttpSettings;
+
+    fn client() -> HttpClient {
+        HttpClient::new(&HttpSettings::default()).unwrap()
+    }
 
     #[test]
     fn test_prepare_url() {
-        let provider = OpenWeather::new("test_key".to_string());
+        let provider = OpenWeather::new("test_key".to_string(), None, client());
         let url = provider.prepare_url(40.71, -74.01);
         let expected = "https://api.openweathermap.org/data/3.0/onecall?lat=40.71&lon=-74.01&appid=test_key&exclude=hourly,minutely&units=metric";
         assert_eq!(url, expected);
     }
+
+    #[test]
+    fn test_prepare_day_summary_url() {
+        let provider = OpenWeather::new("test_key".to_string(), None, client());
+        let date = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+        let url = provider.prepare_day_summary_url(40.71, -74.01, date);
+        let expected = "https://api.openweathermap.org/data/3.0/onecall/day_summary?lat=40.71&lon=-74.01&date=2023-05-01&appid=test_key&units=metric";
+        assert_eq!(url, expected);
+    }
+
+    #[test]
+    fn test_local_noon() {
+        let date = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+        assert_eq!(local_noon(date, Some("+03:00"), 30.52), 1682931600);
+        assert_eq!(local_noon(date, Some("-04:00"), -74.01), 1682956800);
+        // New York's solar noon, without an offset
+        assert_eq!(local_noon(date, None, -74.01), 1682942400 + 17762);
+    }
+
+    #[test]
+    fn test_prepare_timemachine_url() {
+        let provider = OpenWeather::new("test_key".to_string(), None, client());
+        let url = provider.prepare_timemachine_url(40.71, -74.01, 1682942400);
+        let expected = "https://api.openweathermap.org/data/3.0/onecall/timemachine?lat=40.71&lon=-74.01&dt=1682942400&appid=test_key&units=metric";
+        assert_eq!(url, expected);
+    }
+
+    #[test]
+    fn test_prepare_air_pollution_url() {
+        let provider = OpenWeather::new("test_key".to_string(), None, client());
+        let url = provider.prepare_air_pollution_url(40.71, -74.01);
+        let expected = "https://api.openweathermap.org/data/2.5/air_pollution?lat=40.71&lon=-74.01&appid=test_key";
+        assert_eq!(url, expected);
+    }
+
+    #[test]
+    fn test_parse_error_invalid_key() {
+        let body = r#"{"cod":401, "message": "Invalid API key. Please see https://openweathermap.org/faq#error401 for more info."}"#;
+        let err = OpenWeather::parse_error(401, &HeaderMap::new(), body);
+        match err {
+            ProviderError::Unauthorized(failure) => {
+                assert_eq!(failure.status, 401);
+                assert_eq!(failure.code.as_deref(), Some("401"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_quota_with_string_code() {
+        let body = r#"{"cod":"429", "message": "Your account is temporary blocked"}"#;
+        let err = OpenWeather::parse_error(429, &HeaderMap::new(), body);
+        assert!(
+            matches!(err, ProviderError::QuotaExceeded(ref f) if f.code.as_deref() == Some("429"))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_unparsable_body() {
+        let err = OpenWeather::parse_error(502, &HeaderMap::new(), "<html>Bad Gateway</html>");
+        assert!(
+            matches!(err, ProviderError::ServerError(ref f) if f.message == "<html>Bad Gateway</html>")
+        );
+    }
+
+    #[test]
+    fn test_prepare_url_with_custom_base_url() {
+        let provider = OpenWeather::new(
+            "test_key".to_string(),
+            Some("http://127.0.0.1:8080/ow/".to_string()),
+            client(),
+        );
+        let url = provider.prepare_url(40.71, -74.01);
+        assert!(url.starts_with("http://127.0.0.1:8080/ow/onecall?lat=40.71"));
+    }
 }
@@ -0,0 +1,274 @@
+use chrono::NaiveDate;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread::{self, sleep};
+use std::time::{Duration, Instant};
+
+use super::ProviderCapabilities;
+use super::error::{HttpFailure, ProviderError, ProviderResult};
+use super::models::plugin::{PluginError, PluginRequest, PluginResponse};
+use super::provider_trait::WeatherProvider;
+use super::providers_report::ProvidersReport;
+
+/// Version of the JSON documents exchanged with plugins, see README "Plugin Providers".
+pub const PROTOCOL_VERSION: u32 = 1;
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Provider backed by an external executable speaking JSON over stdin/stdout.
+///
+/// The plugin gets one request document on stdin and prints one response document
+/// on stdout. A response carrying a different `protocol` than ours is rejected.
+pub struct Plugin {
+    command: PathBuf,
+    args: Vec<String>,
+    options: BTreeMap<String, Value>,
+    timeout: Duration,
+    capabilities: ProviderCapabilities,
+}
+
+impl Plugin {
+    /// Any date is passed on, the plugin answers `date_out_of_range` itself.
+    pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 36_500,
+        history_days: Some(36_500),
+        hourly: false,
+        alerts: false,
+        air_quality: false,
+        requires_key: false,
+    };
+
+    pub fn new(
+        command: PathBuf,
+        args: Vec<String>,
+        options: BTreeMap<String, Value>,
+        timeout_secs: Option<u64>,
+        capabilities: ProviderCapabilities,
+    ) -> Self {
+        Self {
+            command,
+            args,
+            options,
+            timeout: Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)),
+            capabilities,
+        }
+    }
+
+    /// Runs the plugin once, returning its stdout after it exits.
+    fn call(&self, request: &PluginRequest) -> ProviderResult<Vec<u8>> {
+        let name = self.command.display();
+        let input = serde_json::to_vec(request)
+            .map_err(|e| ProviderError::RequestFailed(format!("plugin {}: {}", name, e)))?;
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                ProviderError::RequestFailed(format!("failed to start plugin {}: {}", name, e))
+            })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // a plugin may answer without reading its input
+            let _ = stdin.write_all(&input);
+        }
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(ProviderError::RequestFailed(format!(
+                        "plugin {} timed out after {}s",
+                        name,
+                        self.timeout.as_secs()
+                    )));
+                }
+                Ok(None) => sleep(POLL_INTERVAL),
+                Err(e) => {
+                    return Err(ProviderError::RequestFailed(format!(
+                        "plugin {}: {}",
+                        name, e
+                    )));
+                }
+            }
+        };
+        let stdout = stdout.join().unwrap_or_default();
+        if !status.success() && stdout.iter().all(u8::is_ascii_whitespace) {
+            let stderr = String::from_utf8_lossy(&stderr.join().unwrap_or_default()).into_owned();
+            return Err(ProviderError::RequestFailed(format!(
+                "plugin {} exited with {}: {}",
+                name,
+                status,
+                stderr.lines().last().unwrap_or_default()
+            )));
+        }
+        Ok(stdout)
+    }
+
+    fn parse_response(output: &[u8]) -> ProviderResult<ProvidersReport> {
+        let response: PluginResponse = serde_json::from_slice(output)
+            .map_err(|e| ProviderError::ParseError(format!("plugin response: {}", e)))?;
+        if response.protocol != PROTOCOL_VERSION {
+            return Err(ProviderError::ParseError(format!(
+                "plugin speaks protocol {}, expected {}",
+                response.protocol, PROTOCOL_VERSION
+            )));
+        }
+        match (response.report, response.error) {
+            (_, Some(error)) => Err(Self::parse_error(error)),
+            (Some(report), None) => Ok(ProvidersReport::from(report)),
+            (None, None) => Err(ProviderError::ParseError(
+                "plugin response has neither report nor error".to_string(),
+            )),
+        }
+    }
+
+    /// Maps a plugin error `kind` onto the error, and exit code, of the matching HTTP failure.
+    fn parse_error(error: PluginError) -> ProviderError {
+        let status = match error.kind.as_str() {
+            "date_out_of_range" => return ProviderError::DateIsOutOfRange(error.message),
+            "unauthorized" => 401,
+            "not_found" => 404,
+            "quota_exceeded" => 429,
+            "unavailable" => 503,
+            _ => 400,
+        };
+        ProviderError::from_http(HttpFailure {
+            status,
+            code: Some(error.kind),
+            message: error.message,
+            retry_after: error.retry_after,
+        })
+    }
+}
+
+impl WeatherProvider for Plugin {
+    fn get_weather(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+    ) -> ProviderResult<ProvidersReport> {
+        let output = self.call(&PluginRequest {
+            protocol: PROTOCOL_VERSION,
+            request: "weather",
+            latitude,
+            longitude,
+            date,
+            options: &self.options,
+        })?;
+        Self::parse_response(&output)
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.capabilities
+    }
+}
+
+/// Reads a child pipe to the end on its own thread, so a chatty plugin never blocks.
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"{"protocol":1,"report":{"min_temp":1.0,"max_temp":9.0,"avg_temp":5.0,
+        "pop":0.2,"summary":"Drizzle","wind_speed":3.0,"humidity":80.0}}"#;
+
+    fn shell(script: &str, timeout_secs: Option<u64>) -> Plugin {
+        Plugin::new(
+            PathBuf::from("sh"),
+            vec!["-c".to_string(), script.to_string()],
+            BTreeMap::from([("station".to_string(), Value::from("kyiv-1"))]),
+            timeout_secs,
+            Plugin::CAPABILITIES,
+        )
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+    }
+
+    #[test]
+    fn test_parse_report() {
+        let report = Plugin::parse_response(REPORT.as_bytes()).unwrap();
+        assert!(report.to_string().contains("Drizzle"));
+    }
+
+    #[test]
+    fn test_protocol_mismatch() {
+        let response = REPORT.replace(r#""protocol":1"#, r#""protocol":2"#);
+        assert!(matches!(
+            Plugin::parse_response(response.as_bytes()),
+            Err(ProviderError::ParseError(msg)) if msg.contains("protocol 2")
+        ));
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let error = |kind: &str| {
+            let response = format!(
+                r#"{{"protocol":1,"error":{{"kind":"{kind}","message":"nope","retry_after":60}}}}"#
+            );
+            Plugin::parse_response(response.as_bytes()).unwrap_err()
+        };
+        assert!(matches!(
+            error("unauthorized"),
+            ProviderError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            error("quota_exceeded"),
+            ProviderError::QuotaExceeded(HttpFailure {
+                retry_after: Some(60),
+                ..
+            })
+        ));
+        assert!(matches!(
+            error("date_out_of_range"),
+            ProviderError::DateIsOutOfRange(_)
+        ));
+        assert!(matches!(error("boom"), ProviderError::ProviderMsgError(_)));
+    }
+
+    #[test]
+    fn test_plugin_receives_request() {
+        // echoes the station option back as the summary
+        let script = r#"station=$(sed 's/.*"station":"\([^"]*\)".*/\1/')
+printf '{"protocol":1,"report":{"min_temp":1,"max_temp":2,"avg_temp":1.5,"pop":0,"summary":"%s","wind_speed":1,"humidity":50}}' "$station""#;
+        let report = shell(script, None)
+            .get_weather(50.45, 30.52, date())
+            .unwrap();
+        assert!(report.to_string().contains("kyiv-1"));
+    }
+
+    #[test]
+    fn test_failing_plugin_reports_stderr() {
+        let err = shell("echo 'feed is down' >&2; exit 3", None)
+            .get_weather(50.45, 30.52, date())
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::RequestFailed(msg) if msg.contains("feed is down")));
+    }
+
+    #[test]
+    fn test_plugin_timeout() {
+        let err = shell("sleep 5", Some(0))
+            .get_weather(50.45, 30.52, date())
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::RequestFailed(msg) if msg.contains("timed out")));
+    }
+}
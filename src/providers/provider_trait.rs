@@ -1,7 +1,11 @@
 use chrono::NaiveDate;
 
+use super::ProviderCapabilities;
 use super::ProviderResult;
 use super::ProvidersReport;
+use super::air_quality::AirQuality;
+use super::alerts::WeatherAlert;
+use super::error::ProviderError;
 
 pub trait WeatherProvider {
     fn get_weather(
@@ -10,4 +14,32 @@ pub trait WeatherProvider {
         longitude: f64,
         date: NaiveDate,
     ) -> ProviderResult<ProvidersReport>;
+
+    /// Reports for every day from `start` to `end` inclusive, one request per day
+    /// unless the provider serves ranges itself.
+    fn get_weather_range(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> ProviderResult<Vec<(NaiveDate, ProvidersReport)>> {
+        start
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .map(|date| Ok((date, self.get_weather(latitude, longitude, date)?)))
+            .collect()
+    }
+
+    /// Alerts in effect at a location now, for providers with `alerts` in their capabilities.
+    fn get_alerts(&self, _latitude: f64, _longitude: f64) -> ProviderResult<Vec<WeatherAlert>> {
+        Err(ProviderError::Unsupported("alerts".to_string()))
+    }
+
+    /// Current pollutant concentrations, for providers with `air_quality` in their capabilities.
+    fn get_air_quality(&self, _latitude: f64, _longitude: f64) -> ProviderResult<AirQuality> {
+        Err(ProviderError::Unsupported("air quality".to_string()))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities;
 }
@@ -1,43 +1,342 @@
+use serde::Deserialize;
 use std::fmt;
 
+use super::alerts::WeatherAlert;
+use super::condition::WeatherCondition;
 use super::error::ProviderError;
-use super::models::{openweather_api::OpenWeatherDaily, weather_api::WeatherApiReport};
+use super::indices::{Index, IndexKind, Inputs, Level};
+use super::met_norway::symbol_summary;
+use super::models::{
+    fixture::FixtureReport,
+    json_api::JsonApiDay,
+    met_norway::MetNorwayForecast,
+    nws::{NwsDay, NwsPeriod},
+    openweather_api::{OpenWeatherDaily, OpenWeatherHistoricalDay, OpenWeatherTimemachineData},
+    visual_crossing::{VisualCrossingDay, VisualCrossingNormal},
+    weather_api::{WeatherApiHour, WeatherApiReport},
+};
+use super::units::{FractionUnit, LengthUnit, SpeedUnit};
+use super::wind::{Beaufort, WindDirection, weighted_direction};
+use crate::astronomy::Astronomy;
+use normalize::{Conventions, normalize};
+pub use normalize::{DataWarning, WindStatistic};
 
+mod normalize;
+
+/// One day of weather. Whatever the provider doesn't report is `None` and shows as `-`.
+#[derive(Debug)]
 pub struct ProvidersReport {
-    min_temp: f32,
-    max_temp: f32,
-    avg_temp: f32,
-    pop: f32,
-    summary: String,
-    wind_speed: f32,
-    humidity: f32,
+    min_temp: Option<f32>,
+    max_temp: Option<f32>,
+    avg_temp: Option<f32>,
+    pop: Option<f32>,
+    /// The provider's own wording, kept alongside `condition`.
+    summary: Option<String>,
+    condition: Option<WeatherCondition>,
+    wind_speed: Option<f32>,
+    humidity: Option<f32>,
     wind_direction: Option<i32>,
+    /// °C, the day's mean where the provider has several.
+    feels_like: Option<f32>,
+    /// °C.
+    dew_point: Option<f32>,
+    /// Sea-level pressure, hPa.
+    pressure: Option<f32>,
+    /// %.
+    cloud_cover: Option<f32>,
+    /// The day's maximum.
+    uv_index: Option<f32>,
+    /// km.
+    visibility: Option<f32>,
+    /// Strongest gust, m/sec.
+    wind_gust: Option<f32>,
+    /// Total over the day, mm.
+    precipitation: Option<f32>,
+    /// Depth of fresh snow over the day, cm.
+    snowfall: Option<f32>,
+    /// Snow over the day as melted water, mm, part of `precipitation`.
+    snow_water: Option<f32>,
+    precipitation_type: Option<PrecipitationType>,
+    normals: Option<ClimateNormals>,
+    /// Alerts in effect on the report's day, shown above it.
+    alerts: Vec<WeatherAlert>,
+    /// Sun and moon for the report's day and place, computed locally.
+    astronomy: Option<Astronomy>,
+    wind_statistic: WindStatistic,
+    /// Values that can't be right, found by the normalization every conversion ends with.
+    warnings: Vec<DataWarning>,
+}
+
+/// Kind of precipitation over the day, the most wintry one when there are several.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrecipitationType {
+    Rain,
+    Snow,
+    Sleet,
+    FreezingRain,
+}
+
+impl PrecipitationType {
+    /// Rain, snow or sleet when both fall, `None` for a dry day.
+    pub fn from_rain_and_snow(rain: bool, snow: bool) -> Option<Self> {
+        match (rain, snow) {
+            (true, true) => Some(Self::Sleet),
+            (true, false) => Some(Self::Rain),
+            (false, true) => Some(Self::Snow),
+            (false, false) => None,
+        }
+    }
+}
+
+impl fmt::Display for PrecipitationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Rain => "rain",
+            Self::Snow => "snow",
+            Self::Sleet => "sleet",
+            Self::FreezingRain => "freezing rain",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Spread of one quantity over the reference years of a climate normal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    pub min: f32,
+    pub mean: f32,
+    pub max: f32,
+}
+
+/// Long-term normals for the calendar day, from providers with climate statistics.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClimateNormals {
+    pub max_temp: Option<Normal>,
+    pub min_temp: Option<Normal>,
+    /// mm.
+    pub precipitation: Option<Normal>,
+    pub humidity: Option<Normal>,
+    /// m/s.
+    pub wind_speed: Option<Normal>,
+}
+
+impl ProvidersReport {
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// Canonical condition, for providers with condition codes.
+    pub fn condition(&self) -> Option<WeatherCondition> {
+        self.condition
+    }
+
+    pub fn normals(&self) -> Option<&ClimateNormals> {
+        self.normals.as_ref()
+    }
+
+    pub fn alerts(&self) -> &[WeatherAlert] {
+        &self.alerts
+    }
+
+    pub(crate) fn with_alerts(mut self, alerts: Vec<WeatherAlert>) -> Self {
+        self.alerts = alerts;
+        self
+    }
+
+    pub fn wind_statistic(&self) -> WindStatistic {
+        self.wind_statistic
+    }
+
+    pub fn warnings(&self) -> &[DataWarning] {
+        &self.warnings
+    }
+
+    pub fn astronomy(&self) -> Option<&Astronomy> {
+        self.astronomy.as_ref()
+    }
+
+    /// Heat-stress and cold-exposure indices in range for the day, none without its
+    /// temperatures.
+    pub fn indices(&self) -> Vec<Index> {
+        let (Some(max_temp), Some(min_temp), Some(avg_temp)) =
+            (self.max_temp, self.min_temp, self.avg_temp)
+        else {
+            return Vec::new();
+        };
+        Index::all(&Inputs {
+            max_temp,
+            min_temp,
+            avg_temp,
+            humidity: self.humidity,
+            wind_speed: self.wind_speed,
+            dew_point: self.dew_point,
+        })
+    }
+
+    /// Nothing known yet, the base every conversion fills in. `normalize` sets the wind
+    /// statistic and the warnings, the callers add alerts and astronomy.
+    fn empty() -> Self {
+        Self {
+            min_temp: None,
+            max_temp: None,
+            avg_temp: None,
+            pop: None,
+            summary: None,
+            condition: None,
+            wind_speed: None,
+            humidity: None,
+            wind_direction: None,
+            feels_like: None,
+            dew_point: None,
+            pressure: None,
+            cloud_cover: None,
+            uv_index: None,
+            visibility: None,
+            wind_gust: None,
+            precipitation: None,
+            snowfall: None,
+            snow_water: None,
+            precipitation_type: None,
+            normals: None,
+            alerts: Vec::new(),
+            astronomy: None,
+            wind_statistic: WindStatistic::Mean,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_astronomy(mut self, astronomy: Astronomy) -> Self {
+        self.astronomy = Some(astronomy);
+        self
+    }
 }
 
 impl fmt::Display for ProvidersReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let wind_dir_str = match self.wind_direction {
-            Some(deg) => format!("{}°", deg),
-            None => "N/A".to_string(),
+        let wind_direction = match self.wind_direction {
+            Some(deg) => format!(" from {}", WindDirection(deg)),
+            None => String::new(),
+        };
+
+        let wind = match self.wind_speed {
+            Some(speed) => format!(
+                "{}{:.1} m/sec ({})",
+                match self.wind_statistic {
+                    WindStatistic::Maximum => "max ",
+                    WindStatistic::Mean | WindStatistic::Sample => "",
+                },
+                speed,
+                Beaufort::from_speed(speed)
+            ),
+            None => "-".to_string(),
         };
 
+        for alert in &self.alerts {
+            writeln!(f, "ALERT         : {}", alert.title())?;
+        }
         write!(
             f,
             "Weather Report:\n\
-            Summary       : {}\n\
-            Temperature   : min {:.1}°C | max {:.1}°C | avg {:.1}°C\n\
-            Precipitation : {:.1}%\n\
-            Humidity      : {:.1}%\n\
-            Wind          : {:.1} m/sec ({})",
-            self.summary,
-            self.min_temp,
-            self.max_temp,
-            self.avg_temp,
-            self.pop,
-            self.humidity,
-            self.wind_speed,
-            wind_dir_str
-        )
+            Summary       : {}{}\n\
+            Temperature   : min {} | max {} | avg {}\n\
+            Precipitation : {}\n\
+            Humidity      : {}\n\
+            Wind          : {}{}",
+            self.summary.as_deref().unwrap_or("-"),
+            match self.condition {
+                Some(condition) => format!("\nCondition     : {}", condition),
+                None => String::new(),
+            },
+            or_dash(self.min_temp, "°C"),
+            or_dash(self.max_temp, "°C"),
+            or_dash(self.avg_temp, "°C"),
+            or_dash(self.pop.map(|pop| pop * 100.), "%"),
+            or_dash(self.humidity, "%"),
+            wind,
+            wind_direction
+        )?;
+        let details = [
+            ("Feels like", self.feels_like, "°C", 1),
+            ("Dew point", self.dew_point, "°C", 1),
+            ("Pressure", self.pressure, " hPa", 0),
+            ("Cloud cover", self.cloud_cover, "%", 0),
+            ("UV index", self.uv_index, "", 1),
+            ("Visibility", self.visibility, " km", 1),
+            ("Wind gusts", self.wind_gust, " m/sec", 1),
+        ];
+        for (name, value, unit, precision) in details {
+            if let Some(value) = value {
+                write!(f, "\n{:<14}: {:.*}{}", name, precision, value, unit)?;
+            }
+        }
+        match (self.precipitation, self.precipitation_type) {
+            (Some(amount), Some(kind)) => {
+                write!(f, "\nPrecip total  : {:.1} mm ({})", amount, kind)?
+            }
+            (Some(amount), None) => write!(f, "\nPrecip total  : {:.1} mm", amount)?,
+            (None, Some(kind)) => write!(f, "\nPrecip type   : {}", kind)?,
+            (None, None) => {}
+        }
+        if let Some(snowfall) = self.snowfall {
+            write!(f, "\nSnowfall      : {:.1} cm", snowfall)?;
+        }
+        if let Some(water) = self.snow_water {
+            write!(f, "\nSnow water eq.: {:.1} mm", water)?;
+        }
+        // only indices past their no-risk band, the apparent temperature from moderate stress
+        // since slight cold stress covers every day below 9°C
+        for index in self.indices() {
+            let shown_above = match index.kind {
+                IndexKind::ApparentTemperature => Level::Low,
+                _ => Level::None,
+            };
+            if index.level > shown_above {
+                write!(f, "\n{:<14}: {}", index.kind.name(), index)?;
+            }
+        }
+        if let Some(normals) = &self.normals {
+            write!(f, "\n{}", normals)?;
+        }
+        if let Some(astronomy) = &self.astronomy {
+            write!(f, "\n{}", astronomy)?;
+        }
+        for warning in &self.warnings {
+            write!(f, "\nData warning  : {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+/// `value` to one decimal with its `unit`, `-` when unknown.
+fn or_dash(value: Option<f32>, unit: &str) -> String {
+    match value {
+        Some(value) => format!("{:.1}{}", value, unit),
+        None => "-".to_string(),
+    }
+}
+
+impl fmt::Display for ClimateNormals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = [
+            ("max", self.max_temp, "°C"),
+            ("min", self.min_temp, "°C"),
+            ("precip", self.precipitation, " mm"),
+            ("humidity", self.humidity, "%"),
+            ("wind", self.wind_speed, " m/sec"),
+        ];
+        let parts: Vec<String> = fields
+            .iter()
+            .filter_map(|(name, normal, unit)| {
+                normal.map(|n| {
+                    format!(
+                        "{} {:.1}{} ({:.1}..{:.1})",
+                        name, n.mean, unit, n.min, n.max
+                    )
+                })
+            })
+            .collect();
+        write!(f, "Normals       : {}", parts.join(" | "))
     }
 }
 impl TryFrom<WeatherApiReport> for ProvidersReport {
@@ -51,51 +350,433 @@ impl TryFrom<WeatherApiReport> for ProvidersReport {
                 "weather api".to_string(),
                 "failed to get forecast".to_string(),
             ))?;
-        Ok(Self {
-            max_temp: day.day.maxtemp_c,
-            min_temp: day.day.mintemp_c,
-            avg_temp: day.day.avgtemp_c,
-            pop: day.day.daily_chance_of_rain,
-            summary: day.day.condition.text.clone(),
-            wind_speed: day.day.maxwind_kph * 1000. / 3600., // km/h -> m/sec
-            wind_direction: None,
-            humidity: day.day.avghumidity,
-        })
+        let hourly = |field: fn(&WeatherApiHour) -> Option<f32>| day.hour.iter().filter_map(field);
+        // the day block only has the maximum, the hours give the mean where they're included
+        let (wind_speed, wind) = match mean(hourly(|hour| hour.wind_kph)) {
+            Some(speed) => (speed, WindStatistic::Mean),
+            None => (day.day.maxwind_kph, WindStatistic::Maximum),
+        };
+        let conventions = Conventions {
+            pop: FractionUnit::Percent,
+            speed: SpeedUnit::Kph,
+            wind,
+            ..Conventions::CANONICAL
+        };
+        let report = Self {
+            max_temp: Some(day.day.maxtemp_c),
+            min_temp: Some(day.day.mintemp_c),
+            avg_temp: Some(day.day.avgtemp_c),
+            pop: Some(day.day.daily_chance_of_rain),
+            summary: Some(day.day.condition.text.clone()),
+            condition: day
+                .day
+                .condition
+                .code
+                .and_then(WeatherCondition::from_weather_api_code),
+            wind_speed: Some(wind_speed),
+            wind_direction: weighted_direction(
+                day.hour
+                    .iter()
+                    .filter_map(|hour| Some((hour.wind_degree?, hour.wind_kph?))),
+            ),
+            feels_like: mean(hourly(|hour| hour.feelslike_c)),
+            dew_point: mean(hourly(|hour| hour.dewpoint_c)),
+            pressure: mean(hourly(|hour| hour.pressure_mb)),
+            cloud_cover: mean(hourly(|hour| hour.cloud)),
+            uv_index: day.day.uv,
+            visibility: day.day.avgvis_km,
+            wind_gust: hourly(|hour| hour.gust_kph).reduce(f32::max),
+            precipitation: day.day.totalprecip_mm,
+            snowfall: day.day.totalsnow_cm,
+            precipitation_type: PrecipitationType::from_rain_and_snow(
+                day.day.daily_will_it_rain == Some(1),
+                day.day.daily_will_it_snow == Some(1),
+            ),
+            humidity: Some(day.day.avghumidity),
+            ..Self::empty()
+        };
+        Ok(normalize(report, conventions))
     }
 }
 
 impl TryFrom<OpenWeatherDaily> for ProvidersReport {
     type Error = ProviderError;
     fn try_from(value: OpenWeatherDaily) -> Result<Self, Self::Error> {
-        Ok(Self {
-            max_temp: value.temp.max,
-            min_temp: value.temp.min,
-            avg_temp: value.temp.day,
+        // rain and snow are left out on days without them
+        let (rain, snow) = (value.rain.unwrap_or(0.), value.snow.unwrap_or(0.));
+        let report = Self {
+            max_temp: Some(value.temp.max),
+            min_temp: Some(value.temp.min),
+            avg_temp: Some(value.temp.day),
+            pop: Some(value.pop),
+            summary: Some(value.summary),
+            condition: value
+                .weather
+                .first()
+                .and_then(|weather| weather.id)
+                .and_then(WeatherCondition::from_open_weather_id),
+            wind_speed: Some(value.wind_speed),
+            humidity: Some(value.humidity),
+            wind_direction: Some(value.wind_deg),
+            feels_like: value.feels_like.map(|feels_like| feels_like.day),
+            dew_point: value.dew_point,
+            pressure: value.pressure,
+            cloud_cover: value.clouds,
+            uv_index: value.uvi,
+            wind_gust: value.wind_gust,
+            precipitation: Some(rain + snow),
+            snow_water: value.snow,
+            precipitation_type: PrecipitationType::from_rain_and_snow(rain > 0., snow > 0.),
+            ..Self::empty()
+        };
+        Ok(normalize(
+            report,
+            Conventions {
+                wind: WindStatistic::Sample,
+                ..Conventions::CANONICAL
+            },
+        ))
+    }
+}
+
+impl TryFrom<OpenWeatherHistoricalDay> for ProvidersReport {
+    type Error = ProviderError;
+    fn try_from(value: OpenWeatherHistoricalDay) -> Result<Self, Self::Error> {
+        let temp = value.summary.temperature;
+        let data = value.snapshot.data;
+        let weather = data.iter().flat_map(|data| &data.weather).next();
+        let summary = weather.map(|condition| condition.description.clone());
+        // the day summary has no feels-like, dew point, UV or visibility, the midday snapshot has
+        let midday =
+            |field: fn(&OpenWeatherTimemachineData) -> Option<f32>| data.first().and_then(field);
+        let report = Self {
+            max_temp: Some(temp.max),
+            min_temp: Some(temp.min),
+            avg_temp: Some((temp.morning + temp.afternoon + temp.evening + temp.night) / 4.),
+            // history has no probability, only the observed total
+            summary,
+            condition: weather
+                .and_then(|weather| weather.id)
+                .and_then(WeatherCondition::from_open_weather_id),
+            wind_speed: Some(value.summary.wind.max.speed),
+            humidity: Some(value.summary.humidity.afternoon),
+            wind_direction: Some(value.summary.wind.max.direction.round() as i32),
+            feels_like: midday(|data| data.feels_like),
+            dew_point: midday(|data| data.dew_point),
+            pressure: value.summary.pressure.map(|pressure| pressure.afternoon),
+            cloud_cover: value.summary.cloud_cover.map(|clouds| clouds.afternoon),
+            uv_index: midday(|data| data.uvi),
+            visibility: midday(|data| data.visibility),
+            wind_gust: midday(|data| data.wind_gust),
+            precipitation: Some(value.summary.precipitation.total),
+            ..Self::empty()
+        };
+        Ok(normalize(
+            report,
+            Conventions {
+                wind: WindStatistic::Maximum,
+                visibility: LengthUnit::M,
+                ..Conventions::CANONICAL
+            },
+        ))
+    }
+}
+
+/// Aggregates the timesteps of one day, the caller drops every other day.
+impl TryFrom<MetNorwayForecast> for ProvidersReport {
+    type Error = ProviderError;
+    fn try_from(value: MetNorwayForecast) -> Result<Self, Self::Error> {
+        let steps = value.properties.timeseries;
+        if steps.is_empty() {
+            return Err(ProviderError::ConvertionError(
+                "met norway".to_string(),
+                "no timesteps for the day".to_string(),
+            ));
+        }
+        let instants = || steps.iter().map(|step| &step.data.instant.details);
+        let temps = || instants().map(|details| details.air_temperature);
+        // hourly steps come with next_1_hours, 6-hourly ones only with next_6_hours
+        let periods = || {
+            steps.iter().filter_map(|step| {
+                step.data
+                    .next_1_hours
+                    .as_ref()
+                    .or(step.data.next_6_hours.as_ref())
+            })
+        };
+        let pop = periods()
+            .filter_map(|period| period.details.probability_of_precipitation)
+            .reduce(f32::max);
+        let mut symbols: Vec<(&str, usize)> = Vec::new();
+        for symbol in periods().filter_map(|period| period.summary.as_ref()) {
+            match symbols
+                .iter_mut()
+                .find(|(code, _)| *code == symbol.symbol_code)
+            {
+                Some((_, seen)) => *seen += 1,
+                None => symbols.push((&symbol.symbol_code, 1)),
+            }
+        }
+        // the most frequent symbol, the earliest on a tie
+        let symbol = symbols
+            .iter()
+            .rev()
+            .max_by_key(|(_, seen)| *seen)
+            .map(|(code, _)| *code);
+        let summary = symbol.map(symbol_summary);
+        let wind_direction =
+            mean_direction(instants().filter_map(|details| details.wind_from_direction));
+        let amounts: Vec<f32> = periods()
+            .filter_map(|period| period.details.precipitation_amount)
+            .collect();
+        let symbol_has = |kind: &str| symbols.iter().any(|(code, _)| code.contains(kind));
+        let precipitation_type = if symbol_has("sleet") {
+            Some(PrecipitationType::Sleet)
+        } else {
+            PrecipitationType::from_rain_and_snow(symbol_has("rain"), symbol_has("snow"))
+        };
+        let report = Self {
+            max_temp: temps().reduce(f32::max),
+            min_temp: temps().reduce(f32::min),
+            avg_temp: mean(temps()),
+            pop,
+            summary,
+            condition: symbol.and_then(WeatherCondition::from_met_norway_symbol),
+            wind_speed: mean(instants().map(|details| details.wind_speed)),
+            humidity: mean(instants().map(|details| details.relative_humidity)),
+            wind_direction,
+            dew_point: mean(instants().filter_map(|details| details.dew_point_temperature)),
+            pressure: mean(instants().filter_map(|details| details.air_pressure_at_sea_level)),
+            cloud_cover: mean(instants().filter_map(|details| details.cloud_area_fraction)),
+            uv_index: instants()
+                .filter_map(|details| details.ultraviolet_index_clear_sky)
+                .reduce(f32::max),
+            wind_gust: instants()
+                .filter_map(|details| details.wind_speed_of_gust)
+                .reduce(f32::max),
+            precipitation: (!amounts.is_empty()).then(|| amounts.iter().sum()),
+            precipitation_type,
+            ..Self::empty()
+        };
+        Ok(normalize(
+            report,
+            Conventions {
+                pop: FractionUnit::Percent,
+                ..Conventions::CANONICAL
+            },
+        ))
+    }
+}
+
+/// Day period gives the high and the summary, night period the low.
+impl TryFrom<NwsDay> for ProvidersReport {
+    type Error = ProviderError;
+    fn try_from(value: NwsDay) -> Result<Self, Self::Error> {
+        let hours = value.hours;
+        let temperature = |period: &NwsPeriod| period.temperature;
+        let day = value.periods.iter().find(|period| period.is_daytime);
+        let night = value.periods.iter().find(|period| !period.is_daytime);
+        let hourly_max = hours.temperature.iter().copied().reduce(f32::max);
+        let hourly_min = hours.temperature.iter().copied().reduce(f32::min);
+        let max_temp = day
+            .map(temperature)
+            .or(hourly_max)
+            .or(night.map(temperature));
+        let min_temp = night
+            .map(temperature)
+            .or(hourly_min)
+            .or(day.map(temperature));
+        let (Some(max_temp), Some(min_temp)) = (max_temp, min_temp) else {
+            return Err(ProviderError::ConvertionError(
+                "nws".to_string(),
+                "no temperature for the day".to_string(),
+            ));
+        };
+        let summary = match (day, night) {
+            (Some(day), Some(night)) if day.short_forecast != night.short_forecast => Some(
+                format!("{}, then {}", day.short_forecast, night.short_forecast),
+            ),
+            (Some(period), _) | (None, Some(period)) => Some(period.short_forecast.clone()),
+            (None, None) => None,
+        };
+        let pop = value
+            .periods
+            .iter()
+            .filter_map(|period| period.probability_of_precipitation.as_ref()?.value)
+            .reduce(f32::max);
+        let report = Self {
+            max_temp: Some(max_temp),
+            min_temp: Some(min_temp),
+            avg_temp: mean(hours.temperature.iter().copied()).or(Some((min_temp + max_temp) / 2.)),
+            pop,
+            summary,
+            wind_speed: mean(hours.wind_speed.iter().copied()),
+            humidity: mean(hours.humidity.iter().copied()),
+            wind_direction: mean_direction(hours.wind_direction.iter().copied()),
+            feels_like: mean(hours.apparent_temperature.iter().copied()),
+            dew_point: mean(hours.dew_point.iter().copied()),
+            cloud_cover: mean(hours.sky_cover.iter().copied()),
+            visibility: mean(hours.visibility.iter().copied()),
+            wind_gust: hours.wind_gust.iter().copied().reduce(f32::max),
+            precipitation: hours.precipitation,
+            snowfall: hours.snow,
+            ..Self::empty()
+        };
+        Ok(normalize(
+            report,
+            Conventions {
+                temperature: value.temperature_unit,
+                pop: FractionUnit::Percent,
+                speed: value.speed_unit,
+                snowfall: LengthUnit::Mm,
+                visibility: LengthUnit::M,
+                ..Conventions::CANONICAL
+            },
+        ))
+    }
+}
+
+impl From<FixtureReport> for ProvidersReport {
+    fn from(value: FixtureReport) -> Self {
+        let report = Self {
+            max_temp: value.max_temp,
+            min_temp: value.min_temp,
+            avg_temp: value.avg_temp,
             pop: value.pop,
             summary: value.summary,
             wind_speed: value.wind_speed,
             humidity: value.humidity,
-            wind_direction: Some(value.wind_deg),
-        })
+            wind_direction: value.wind_direction,
+            feels_like: value.feels_like,
+            dew_point: value.dew_point,
+            pressure: value.pressure,
+            cloud_cover: value.cloud_cover,
+            uv_index: value.uv_index,
+            visibility: value.visibility,
+            wind_gust: value.wind_gust,
+            precipitation: value.precipitation,
+            snowfall: value.snowfall,
+            snow_water: value.snow_water,
+            precipitation_type: value.precipitation_type,
+            ..Self::empty()
+        };
+        normalize(report, Conventions::CANONICAL)
+    }
+}
+
+impl From<JsonApiDay> for ProvidersReport {
+    fn from(value: JsonApiDay) -> Self {
+        let report = Self {
+            max_temp: Some(value.max_temp),
+            min_temp: Some(value.min_temp),
+            avg_temp: value
+                .avg_temp
+                .or(Some((value.min_temp + value.max_temp) / 2.)),
+            pop: Some(value.pop),
+            summary: value.summary,
+            wind_speed: Some(value.wind_speed),
+            humidity: Some(value.humidity),
+            wind_direction: value.wind_direction.map(|deg| deg.round() as i32),
+            ..Self::empty()
+        };
+        normalize(
+            report,
+            Conventions {
+                temperature: value.units.temperature,
+                pop: value.units.pop,
+                speed: value.units.wind_speed,
+                ..Conventions::CANONICAL
+            },
+        )
+    }
+}
+
+impl TryFrom<VisualCrossingDay> for ProvidersReport {
+    type Error = ProviderError;
+    fn try_from(value: VisualCrossingDay) -> Result<Self, Self::Error> {
+        let normal = |normal: VisualCrossingNormal| match normal? {
+            [Some(min), Some(mean), Some(max)] => Some(Normal { min, mean, max }),
+            _ => None,
+        };
+        let normals = value.normal.map(|n| ClimateNormals {
+            max_temp: normal(n.tempmax),
+            min_temp: normal(n.tempmin),
+            precipitation: normal(n.precip),
+            humidity: normal(n.humidity),
+            wind_speed: normal(n.windspeed),
+        });
+        let kinds = value.preciptype.unwrap_or_default();
+        let has = |kind: &str| kinds.iter().any(|k| k == kind);
+        let precipitation_type = if has("freezingrain") {
+            Some(PrecipitationType::FreezingRain)
+        } else if has("ice") {
+            Some(PrecipitationType::Sleet)
+        } else {
+            PrecipitationType::from_rain_and_snow(has("rain"), has("snow"))
+        };
+        let report = Self {
+            max_temp: Some(value.tempmax),
+            min_temp: Some(value.tempmin),
+            avg_temp: Some(value.temp),
+            // observed days have no probability, only the measured total
+            pop: value.precipprob,
+            summary: Some(value.conditions),
+            wind_speed: Some(value.windspeed),
+            humidity: Some(value.humidity),
+            wind_direction: value.winddir.map(|deg| deg.round() as i32 % 360),
+            feels_like: value.feelslike,
+            dew_point: value.dew,
+            pressure: value.pressure,
+            cloud_cover: value.cloudcover,
+            uv_index: value.uvindex,
+            visibility: value.visibility,
+            wind_gust: value.windgust,
+            precipitation: value.precip,
+            snowfall: value.snow,
+            precipitation_type,
+            normals: normals.filter(|normals| *normals != ClimateNormals::default()),
+            ..Self::empty()
+        };
+        Ok(normalize(
+            report,
+            Conventions {
+                pop: FractionUnit::Percent,
+                speed: SpeedUnit::Kph,
+                wind: WindStatistic::Maximum,
+                ..Conventions::CANONICAL
+            },
+        ))
     }
 }
 
+/// Arithmetic mean, `None` without values.
+fn mean(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0., 0), |(sum, count), value| (sum + value, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// Circular mean of directions in degrees, `None` when they cancel out or are missing.
+fn mean_direction(degrees: impl Iterator<Item = f32>) -> Option<i32> {
+    weighted_direction(degrees.map(|degrees| (degrees, 1.)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::providers::models::{openweather_api::*, weather_api::*};
+    use crate::providers::models::{fixture::FixtureReport, openweather_api::*, weather_api::*};
 
     #[test]
     fn test_report_display_with_wind_direction() {
         let report = ProvidersReport {
-            min_temp: 10.0,
-            max_temp: 20.0,
-            avg_temp: 15.0,
-            pop: 50.5,
-            summary: "Sunny".to_string(),
-            wind_speed: 5.5,
-            humidity: 70.0,
+            min_temp: Some(10.0),
+            max_temp: Some(20.0),
+            avg_temp: Some(15.0),
+            pop: Some(0.505),
+            summary: Some("Sunny".to_string()),
+            wind_speed: Some(5.5),
+            humidity: Some(70.0),
             wind_direction: Some(180),
+            ..ProvidersReport::empty()
         };
         let expected = "\
 Weather Report:\n\
@@ -103,15 +784,90 @@ Summary       : Sunny\n\
 Temperature   : min 10.0°C | max 20.0°C | avg 15.0°C\n\
 Precipitation : 50.5%\n\
 Humidity      : 70.0%\n\
-Wind          : 5.5 m/sec (180°)";
+Wind          : 5.5 m/sec (Beaufort 4, moderate breeze) from S 180° ↑";
         assert_eq!(format!("{}", report), expected);
     }
 
+    #[test]
+    fn test_report_display_shows_known_details_only() {
+        let report = ProvidersReport::from(FixtureReport {
+            min_temp: Some(-2.0),
+            max_temp: Some(1.0),
+            avg_temp: Some(-0.5),
+            pop: Some(0.8),
+            summary: Some("Snow".to_string()),
+            wind_speed: Some(4.0),
+            humidity: Some(90.0),
+            feels_like: Some(-5.25),
+            pressure: Some(1003.4),
+            wind_gust: Some(11.0),
+            precipitation: Some(6.0),
+            snowfall: Some(4.0),
+            precipitation_type: Some(PrecipitationType::Sleet),
+            ..FixtureReport::default()
+        });
+        let text = report.to_string();
+        assert!(
+            text.ends_with(
+                "Wind          : 4.0 m/sec (Beaufort 3, gentle breeze)\n\
+                 Feels like    : -5.2°C\n\
+                 Pressure      : 1003 hPa\n\
+                 Wind gusts    : 11.0 m/sec\n\
+                 Precip total  : 6.0 mm (sleet)\n\
+                 Snowfall      : 4.0 cm\n\
+                 Apparent temp : -5.6°C (moderate cold stress)"
+            ),
+            "{text}"
+        );
+        assert!(!text.contains("Dew point"));
+    }
+
+    #[test]
+    fn test_report_display_leaves_out_slight_cold_stress() {
+        let report = ProvidersReport::from(FixtureReport {
+            min_temp: Some(3.0),
+            max_temp: Some(9.0),
+            avg_temp: Some(6.0),
+            humidity: Some(75.0),
+            wind_speed: Some(3.0),
+            ..FixtureReport::default()
+        });
+        let apparent = report
+            .indices()
+            .into_iter()
+            .find(|index| index.kind == IndexKind::ApparentTemperature)
+            .unwrap();
+        assert_eq!(apparent.category, "slight cold stress");
+        assert_eq!(apparent.level, Level::Low);
+        assert!(!report.to_string().contains("Apparent temp"), "{report}");
+    }
+
+    #[test]
+    fn test_report_display_marks_missing_values() {
+        let report = ProvidersReport::from(FixtureReport {
+            min_temp: Some(8.0),
+            max_temp: Some(14.0),
+            wind_direction: Some(270),
+            ..FixtureReport::default()
+        });
+        let expected = "\
+Weather Report:\n\
+Summary       : -\n\
+Temperature   : min 8.0°C | max 14.0°C | avg -\n\
+Precipitation : -\n\
+Humidity      : -\n\
+Wind          : - from W 270° →";
+        assert_eq!(report.to_string(), expected);
+        assert!(report.warnings.is_empty());
+        assert!(report.indices().is_empty());
+    }
+
     #[test]
     fn test_try_from_weather_api_report() {
         let api_report = WeatherApiReport {
             forecast: WeatherApiForecast {
                 forecastday: vec![WeatherApiForecastDay {
+                    date: chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
                     day: WeatherApiDay {
                         maxtemp_c: 25.0,
                         mintemp_c: 15.0,
@@ -121,19 +877,77 @@ Wind          : 5.5 m/sec (180°)";
                         daily_chance_of_rain: 10.0,
                         condition: WeatherApiCondition {
                             text: "Partly cloudy".to_string(),
+                            code: Some(1003),
                         },
+                        totalprecip_mm: Some(1.2),
+                        totalsnow_cm: Some(0.5),
+                        avgvis_km: Some(9.0),
+                        uv: Some(3.0),
+                        daily_will_it_rain: Some(1),
+                        daily_will_it_snow: Some(0),
                     },
+                    hour: vec![
+                        WeatherApiHour {
+                            feelslike_c: Some(14.0),
+                            dewpoint_c: Some(8.0),
+                            pressure_mb: Some(1012.0),
+                            cloud: Some(20.0),
+                            wind_kph: None,
+                            wind_degree: None,
+                            gust_kph: Some(18.0),
+                        },
+                        WeatherApiHour {
+                            feelslike_c: Some(22.0),
+                            dewpoint_c: Some(10.0),
+                            pressure_mb: Some(1010.0),
+                            cloud: None,
+                            wind_kph: None,
+                            wind_degree: None,
+                            gust_kph: Some(54.0),
+                        },
+                    ],
                 }],
             },
+            alerts: WeatherApiAlerts::default(),
         };
 
         let report = ProvidersReport::try_from(api_report).unwrap();
-        assert_eq!(report.max_temp, 25.0);
-        assert_eq!(report.min_temp, 15.0);
-        assert_eq!(report.avg_temp, 20.0);
+        assert_eq!(report.max_temp, Some(25.0));
+        assert_eq!(report.min_temp, Some(15.0));
+        assert_eq!(report.avg_temp, Some(20.0));
         // Test kph to m/sec conversion: 36 kph * 1000 / 3600 = 10 m/sec
-        assert!((report.wind_speed - 10.0).abs() < 0.001);
-        assert_eq!(report.summary, "Partly cloudy");
+        assert!((report.wind_speed.unwrap() - 10.0).abs() < 0.001);
+        assert_eq!(report.wind_statistic, WindStatistic::Maximum);
+        assert_eq!(report.summary(), Some("Partly cloudy"));
+        assert_eq!(report.condition, Some(WeatherCondition::PartlyCloudy));
+        assert_eq!(report.feels_like, Some(18.0));
+        assert_eq!(report.dew_point, Some(9.0));
+        assert_eq!(report.pressure, Some(1011.0));
+        assert_eq!(report.cloud_cover, Some(20.0));
+        assert!((report.wind_gust.unwrap() - 15.0).abs() < 0.001);
+        assert_eq!(report.precipitation, Some(1.2));
+        assert_eq!(report.snowfall, Some(0.5));
+        assert_eq!(report.snow_water, None);
+        assert_eq!(report.precipitation_type, Some(PrecipitationType::Rain));
+    }
+
+    #[test]
+    fn test_weather_api_dominant_wind_direction() {
+        // a strong westerly afternoon outweighs a light easterly night
+        let api_report: WeatherApiReport = serde_json::from_str(
+            r#"{"forecast":{"forecastday":[{"date":"2025-01-01","day":{
+                "maxtemp_c":8,"mintemp_c":2,"avgtemp_c":5,"maxwind_kph":40,"avghumidity":80,
+                "daily_chance_of_rain":0,"condition":{"text":"Sunny"}},
+                "hour":[{"wind_kph":4,"wind_degree":90,"wind_dir":"E"},
+                        {"wind_kph":4,"wind_degree":100,"wind_dir":"E"},
+                        {"wind_kph":30,"wind_degree":260,"wind_dir":"W"},
+                        {"wind_kph":40,"wind_degree":280,"wind_dir":"W"}]}]}}"#,
+        )
+        .unwrap();
+
+        let report = ProvidersReport::try_from(api_report).unwrap();
+        assert_eq!(report.wind_direction, Some(271));
+        assert!(report.to_string().contains("from W 271° →"));
     }
 
     #[test]
@@ -150,15 +964,96 @@ Wind          : 5.5 m/sec (180°)";
             wind_speed: 7.5,
             wind_deg: 90,
             pop: 0.1,
+            weather: vec![OpenWeatherCondition {
+                id: Some(800),
+                description: "clear sky".to_string(),
+            }],
+            feels_like: Some(OpenWeatherFeelsLike { day: 17.0 }),
+            dew_point: Some(9.5),
+            pressure: Some(1018.0),
+            clouds: Some(5.0),
+            uvi: Some(6.2),
+            wind_gust: Some(12.0),
+            rain: Some(0.4),
+            snow: None,
         };
 
         let report = ProvidersReport::try_from(ow_daily).unwrap();
-        assert_eq!(report.max_temp, 22.0);
-        assert_eq!(report.min_temp, 12.0);
-        assert_eq!(report.avg_temp, 18.0);
-        assert!((report.pop - 0.1).abs() < 0.001);
-        assert_eq!(report.summary, "Clear sky");
-        assert_eq!(report.wind_speed, 7.5);
+        assert_eq!(report.max_temp, Some(22.0));
+        assert_eq!(report.min_temp, Some(12.0));
+        assert_eq!(report.avg_temp, Some(18.0));
+        assert!((report.pop.unwrap() - 0.1).abs() < 0.001);
+        assert_eq!(report.summary(), Some("Clear sky"));
+        assert_eq!(report.condition, Some(WeatherCondition::Clear));
+        assert!(
+            report
+                .to_string()
+                .contains("Summary       : Clear sky\nCondition     : clear\n")
+        );
+        assert_eq!(report.wind_speed, Some(7.5));
         assert_eq!(report.wind_direction, Some(90));
+        assert_eq!(report.feels_like, Some(17.0));
+        assert_eq!(report.uv_index, Some(6.2));
+        assert_eq!(report.visibility, None);
+        assert_eq!(report.precipitation, Some(0.4));
+        assert_eq!(report.snowfall, None);
+        assert_eq!(report.snow_water, None);
+        assert_eq!(report.precipitation_type, Some(PrecipitationType::Rain));
+    }
+
+    #[test]
+    fn test_open_weather_snow_is_water_equivalent() {
+        let ow_daily: OpenWeatherDaily = serde_json::from_str(
+            r#"{"dt":1735732800,"summary":"Snow","temp":{"day":-2,"min":-5,"max":0},
+                "humidity":90,"wind_speed":3,"wind_deg":10,"pop":0.9,"snow":2.5}"#,
+        )
+        .unwrap();
+        let report = ProvidersReport::try_from(ow_daily).unwrap();
+        assert_eq!(report.snowfall, None);
+        assert_eq!(report.snow_water, Some(2.5));
+        assert_eq!(report.precipitation, Some(2.5));
+        let text = report.to_string();
+        assert!(text.contains("Snow water eq.: 2.5 mm"), "{text}");
+        assert!(!text.contains("Snowfall"), "{text}");
+    }
+
+    #[test]
+    fn test_try_from_open_weather_historical_day() {
+        let summary: OpenWeatherDaySummary = serde_json::from_str(
+            r#"{
+                "lat": 33, "lon": 35, "tz": "+02:00", "date": "2023-05-01", "units": "metric",
+                "cloud_cover": {"afternoon": 0},
+                "humidity": {"afternoon": 33},
+                "precipitation": {"total": 1.5},
+                "temperature": {"min": 10, "max": 22, "afternoon": 20, "night": 12, "evening": 18, "morning": 14},
+                "pressure": {"afternoon": 1015},
+                "wind": {"max": {"speed": 8.7, "direction": 120}}
+            }"#,
+        )
+        .unwrap();
+        let snapshot: OpenWeatherTimemachine = serde_json::from_str(
+            r#"{"lat": 33, "lon": 35, "data": [{"dt": 1682942400, "feels_like": 19.1, "visibility": 8000, "weather": [{"id": 800, "main": "Clear", "description": "clear sky"}]}]}"#,
+        )
+        .unwrap();
+
+        let report =
+            ProvidersReport::try_from(OpenWeatherHistoricalDay { summary, snapshot }).unwrap();
+        assert_eq!(report.max_temp, Some(22.0));
+        assert_eq!(report.min_temp, Some(10.0));
+        assert_eq!(report.avg_temp, Some(16.0));
+        // history has no probability, only the observed total
+        assert_eq!(report.pop, None);
+        assert!(report.to_string().contains("Precipitation : -\n"));
+        assert_eq!(report.summary(), Some("clear sky"));
+        assert_eq!(report.condition, Some(WeatherCondition::Clear));
+        assert_eq!(report.humidity, Some(33.0));
+        assert_eq!(report.wind_speed, Some(8.7));
+        assert_eq!(report.wind_direction, Some(120));
+        assert_eq!(report.pressure, Some(1015.0));
+        assert_eq!(report.cloud_cover, Some(0.0));
+        assert_eq!(report.precipitation, Some(1.5));
+        assert_eq!(report.feels_like, Some(19.1));
+        assert_eq!(report.visibility, Some(8.0));
+        assert_eq!(report.dew_point, None);
     }
 }
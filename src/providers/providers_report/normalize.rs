@@ -0,0 +1,397 @@
+use std::fmt;
+
+use super::{Normal, ProvidersReport};
+use crate::providers::units::{FractionUnit, LengthUnit, SpeedUnit, TemperatureUnit};
+
+/// What a provider's daily wind speed stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindStatistic {
+    /// Mean over the day, what the report means by wind speed.
+    Mean,
+    /// Strongest sustained wind of the day, for providers without anything finer.
+    Maximum,
+    /// A single reading the provider gives for the whole day.
+    Sample,
+}
+
+/// How a provider states the values providers disagree on. Each conversion names what
+/// differs from `CANONICAL` and leaves every value in the provider's own unit.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Conventions {
+    /// Unit of the temperatures, feels-like, dew point and temperature normals.
+    pub(crate) temperature: TemperatureUnit,
+    pub(crate) pop: FractionUnit,
+    /// Unit of the wind speed, gusts and wind normals.
+    pub(crate) speed: SpeedUnit,
+    pub(crate) wind: WindStatistic,
+    pub(crate) snowfall: LengthUnit,
+    pub(crate) visibility: LengthUnit,
+}
+
+impl Conventions {
+    /// °C, probability 0-1, m/s, a daily mean wind, snowfall in cm and visibility in km,
+    /// as fixtures and plugins deliver.
+    pub(crate) const CANONICAL: Self = Self {
+        temperature: TemperatureUnit::Celsius,
+        pop: FractionUnit::Fraction,
+        speed: SpeedUnit::Mps,
+        wind: WindStatistic::Mean,
+        snowfall: LengthUnit::Cm,
+        visibility: LengthUnit::Km,
+    };
+}
+
+/// A reported value that can't be right, kept as reported and shown under the report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataWarning {
+    OutOfRange {
+        field: &'static str,
+        value: f32,
+        min: f32,
+        max: f32,
+    },
+    MinAboveMax {
+        min: f32,
+        max: f32,
+    },
+    AverageOutsideRange {
+        avg: f32,
+        min: f32,
+        max: f32,
+    },
+}
+
+impl fmt::Display for DataWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(f, "{} {:.1} outside {}..{}", field, value, min, max),
+            Self::MinAboveMax { min, max } => {
+                write!(f, "min temperature {:.1}°C above max {:.1}°C", min, max)
+            }
+            Self::AverageOutsideRange { avg, min, max } => write!(
+                f,
+                "avg temperature {:.1}°C outside min..max {:.1}..{:.1}°C",
+                avg, min, max
+            ),
+        }
+    }
+}
+
+/// Converts `report` from the provider's `conventions` to canonical units and semantics,
+/// then checks every value against physical limits. The only place units are converted.
+pub(super) fn normalize(mut report: ProvidersReport, conventions: Conventions) -> ProvidersReport {
+    let celsius = |temp: f32| conventions.temperature.to_celsius(temp);
+    let mps = |speed: f32| conventions.speed.to_mps(speed);
+    report.min_temp = report.min_temp.map(celsius);
+    report.max_temp = report.max_temp.map(celsius);
+    report.avg_temp = report.avg_temp.map(celsius);
+    report.feels_like = report.feels_like.map(celsius);
+    report.dew_point = report.dew_point.map(celsius);
+    report.pop = report.pop.map(|pop| conventions.pop.to_fraction(pop));
+    report.wind_speed = report.wind_speed.map(mps);
+    report.wind_gust = report.wind_gust.map(mps);
+    report.snowfall = report
+        .snowfall
+        .map(|depth| conventions.snowfall.to_cm(depth));
+    report.visibility = report
+        .visibility
+        .map(|distance| conventions.visibility.to_km(distance));
+    if let Some(normals) = &mut report.normals {
+        normals.max_temp = normals.max_temp.map(|normal| convert(normal, celsius));
+        normals.min_temp = normals.min_temp.map(|normal| convert(normal, celsius));
+        normals.wind_speed = normals.wind_speed.map(|normal| convert(normal, mps));
+    }
+    report.wind_statistic = conventions.wind;
+    report.warnings = check(&report);
+    report
+}
+
+fn convert(normal: Normal, unit: impl Fn(f32) -> f32) -> Normal {
+    Normal {
+        min: unit(normal.min),
+        mean: unit(normal.mean),
+        max: unit(normal.max),
+    }
+}
+
+/// Limits are wide, past the records on Earth, so only broken data trips them.
+fn check(report: &ProvidersReport) -> Vec<DataWarning> {
+    let mut warnings = Vec::new();
+    match (report.min_temp, report.max_temp, report.avg_temp) {
+        (Some(min), Some(max), _) if min > max => {
+            warnings.push(DataWarning::MinAboveMax { min, max });
+        }
+        (Some(min), Some(max), Some(avg)) if !(min..=max).contains(&avg) => {
+            warnings.push(DataWarning::AverageOutsideRange { avg, min, max });
+        }
+        _ => {}
+    }
+    let limits = [
+        ("min temperature", report.min_temp, -95., 60.),
+        ("max temperature", report.max_temp, -95., 60.),
+        ("avg temperature", report.avg_temp, -95., 60.),
+        ("precipitation probability", report.pop, 0., 1.),
+        ("humidity", report.humidity, 0., 100.),
+        ("wind speed", report.wind_speed, 0., 120.),
+        (
+            "wind direction",
+            report.wind_direction.map(|deg| deg as f32),
+            0.,
+            360.,
+        ),
+        ("feels like", report.feels_like, -100., 80.),
+        ("dew point", report.dew_point, -100., 40.),
+        ("pressure", report.pressure, 850., 1090.),
+        ("cloud cover", report.cloud_cover, 0., 100.),
+        ("UV index", report.uv_index, 0., 25.),
+        ("visibility", report.visibility, 0., 1000.),
+        ("wind gusts", report.wind_gust, 0., 120.),
+        ("precipitation", report.precipitation, 0., 2000.),
+        ("snowfall", report.snowfall, 0., 1000.),
+        ("snow water equivalent", report.snow_water, 0., 2000.),
+    ];
+    for (field, value, min, max) in limits {
+        match value {
+            Some(value) if !(min..=max).contains(&value) => {
+                warnings.push(DataWarning::OutOfRange {
+                    field,
+                    value,
+                    min,
+                    max,
+                });
+            }
+            _ => {}
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::models::{
+        fixture::FixtureReport,
+        met_norway::MetNorwayForecast,
+        nws::{NwsDay, NwsHours},
+        openweather_api::{OpenWeatherDaily, OpenWeatherHistoricalDay},
+        visual_crossing::VisualCrossingDay,
+        weather_api::WeatherApiReport,
+    };
+    use serde_json::from_str;
+
+    fn assert_close(actual: Option<f32>, expected: f32) {
+        let actual = actual.expect("value expected");
+        assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_weather_api_percent_and_hourly_mean() {
+        let body: WeatherApiReport = from_str(
+            r#"{"forecast":{"forecastday":[{"date":"2025-01-01","day":{
+                "maxtemp_c":8,"mintemp_c":2,"avgtemp_c":5,"maxwind_kph":50,"avghumidity":80,
+                "daily_chance_of_rain":10,"condition":{"text":"Light rain"}},
+                "hour":[{"wind_kph":18,"gust_kph":30},{"wind_kph":36,"gust_kph":54}]}]}}"#,
+        )
+        .unwrap();
+        let report = ProvidersReport::try_from(body).unwrap();
+        assert_close(report.pop, 0.1);
+        assert_close(report.wind_speed, 7.5);
+        assert_eq!(report.wind_statistic, WindStatistic::Mean);
+        assert_close(report.wind_gust, 15.);
+        assert!(report.to_string().contains("Precipitation : 10.0%"));
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_weather_api_without_hours_is_daily_maximum() {
+        let body: WeatherApiReport = from_str(
+            r#"{"forecast":{"forecastday":[{"date":"2025-01-01","day":{
+                "maxtemp_c":8,"mintemp_c":2,"avgtemp_c":5,"maxwind_kph":36,"avghumidity":80,
+                "daily_chance_of_rain":100,"condition":{"text":"Rain"}}}]}}"#,
+        )
+        .unwrap();
+        let report = ProvidersReport::try_from(body).unwrap();
+        assert_close(report.pop, 1.);
+        assert_close(report.wind_speed, 10.);
+        assert_eq!(report.wind_statistic, WindStatistic::Maximum);
+        assert!(
+            report
+                .to_string()
+                .contains("Wind          : max 10.0 m/sec")
+        );
+    }
+
+    #[test]
+    fn test_open_weather_daily_is_a_sample() {
+        let body: OpenWeatherDaily = from_str(
+            r#"{"dt":1735732800,"summary":"Clear","temp":{"day":18,"min":12,"max":22},
+                "humidity":65,"wind_speed":7.5,"wind_deg":90,"pop":0.1,"wind_gust":11}"#,
+        )
+        .unwrap();
+        let report = ProvidersReport::try_from(body).unwrap();
+        assert_close(report.pop, 0.1);
+        assert_close(report.wind_speed, 7.5);
+        assert_close(report.wind_gust, 11.);
+        assert_eq!(report.wind_statistic, WindStatistic::Sample);
+    }
+
+    #[test]
+    fn test_open_weather_history_wind_is_maximum() {
+        let day = OpenWeatherHistoricalDay {
+            summary: from_str(
+                r#"{"humidity":{"afternoon":40},"precipitation":{"total":0},
+                    "temperature":{"min":8,"max":20,"morning":10,"afternoon":19,"evening":15,"night":9},
+                    "wind":{"max":{"speed":5.1,"direction":320}}}"#,
+            )
+            .unwrap(),
+            snapshot: from_str(r#"{"data":[]}"#).unwrap(),
+        };
+        let report = ProvidersReport::try_from(day).unwrap();
+        assert_eq!(report.pop, None);
+        assert_close(report.wind_speed, 5.1);
+        assert_eq!(report.wind_statistic, WindStatistic::Maximum);
+    }
+
+    #[test]
+    fn test_met_norway_mean_of_steps() {
+        let body: MetNorwayForecast = from_str(
+            r#"{"properties":{"timeseries":[
+                {"time":"2025-07-01T00:00:00Z","data":{
+                    "instant":{"details":{"air_temperature":12,"relative_humidity":80,"wind_speed":2}},
+                    "next_1_hours":{"summary":{"symbol_code":"rain"},
+                        "details":{"precipitation_amount":0.4,"probability_of_precipitation":30}}}},
+                {"time":"2025-07-01T01:00:00Z","data":{
+                    "instant":{"details":{"air_temperature":11,"relative_humidity":84,"wind_speed":4}},
+                    "next_1_hours":{"summary":{"symbol_code":"cloudy"},
+                        "details":{"precipitation_amount":0,"probability_of_precipitation":60}}}}]}}"#,
+        )
+        .unwrap();
+        let report = ProvidersReport::try_from(body).unwrap();
+        assert_close(report.pop, 0.6);
+        assert_close(report.wind_speed, 3.);
+        assert_eq!(report.wind_statistic, WindStatistic::Mean);
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_nws_fahrenheit_kph_percent() {
+        let day = NwsDay {
+            periods: from_str(
+                r#"[{"startTime":"2025-07-01T06:00:00-04:00","isDaytime":true,"temperature":86,
+                     "temperatureUnit":"F","probabilityOfPrecipitation":{"value":40},
+                     "shortForecast":"Showers"}]"#,
+            )
+            .unwrap(),
+            hours: NwsHours {
+                temperature: vec![68., 77.],
+                humidity: vec![60., 70.],
+                wind_speed: vec![14.4, 21.6],
+                ..NwsHours::default()
+            },
+            temperature_unit: TemperatureUnit::Fahrenheit,
+            speed_unit: SpeedUnit::Kph,
+        };
+        let report = ProvidersReport::try_from(day).unwrap();
+        assert_close(report.max_temp, 30.);
+        assert_close(report.min_temp, 20.);
+        assert_close(report.avg_temp, 22.5);
+        assert_close(report.pop, 0.4);
+        assert_close(report.wind_speed, 5.);
+        assert_eq!(report.wind_statistic, WindStatistic::Mean);
+    }
+
+    #[test]
+    fn test_visual_crossing_kph_maximum() {
+        let body: VisualCrossingDay = from_str(
+            r#"{"datetime":"2025-07-01","tempmax":24,"tempmin":14,"temp":19,"humidity":55,
+                "precipprob":20,"precip":0.2,"windspeed":36,"windgust":72,"winddir":250,
+                "conditions":"Partially cloudy"}"#,
+        )
+        .unwrap();
+        let report = ProvidersReport::try_from(body).unwrap();
+        assert_close(report.pop, 0.2);
+        assert_close(report.wind_speed, 10.);
+        assert_close(report.wind_gust, 20.);
+        assert_eq!(report.wind_statistic, WindStatistic::Maximum);
+    }
+
+    #[test]
+    fn test_visual_crossing_observation_has_no_probability() {
+        let body: VisualCrossingDay = from_str(
+            r#"{"datetime":"1995-08-03","tempmax":24,"tempmin":14,"temp":19,"humidity":80,
+                "precip":6.2,"windspeed":21.6,"conditions":"Rain, Overcast"}"#,
+        )
+        .unwrap();
+        let report = ProvidersReport::try_from(body).unwrap();
+        assert_eq!(report.pop, None);
+        assert_eq!(report.precipitation, Some(6.2));
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_flagged_and_kept() {
+        let report = ProvidersReport::from(FixtureReport {
+            min_temp: Some(21.),
+            max_temp: Some(18.),
+            avg_temp: Some(19.),
+            pop: Some(0.3),
+            summary: Some("Cloudy".to_string()),
+            wind_speed: Some(3.),
+            humidity: Some(104.),
+            cloud_cover: Some(-5.),
+            ..FixtureReport::default()
+        });
+        assert_eq!(report.humidity, Some(104.));
+        assert_eq!(
+            report.warnings,
+            vec![
+                DataWarning::MinAboveMax { min: 21., max: 18. },
+                DataWarning::OutOfRange {
+                    field: "humidity",
+                    value: 104.,
+                    min: 0.,
+                    max: 100.
+                },
+                DataWarning::OutOfRange {
+                    field: "cloud cover",
+                    value: -5.,
+                    min: 0.,
+                    max: 100.
+                },
+            ]
+        );
+        let text = report.to_string();
+        assert!(
+            text.ends_with(
+                "Data warning  : min temperature 21.0°C above max 18.0°C\n\
+                 Data warning  : humidity 104.0 outside 0..100\n\
+                 Data warning  : cloud cover -5.0 outside 0..100"
+            ),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn test_average_outside_min_and_max() {
+        let report = ProvidersReport::from(FixtureReport {
+            min_temp: Some(10.),
+            max_temp: Some(18.),
+            avg_temp: Some(25.),
+            summary: Some("Clear".to_string()),
+            humidity: Some(50.),
+            ..FixtureReport::default()
+        });
+        assert_eq!(
+            report.warnings,
+            vec![DataWarning::AverageOutsideRange {
+                avg: 25.,
+                min: 10.,
+                max: 18.
+            }]
+        );
+    }
+}
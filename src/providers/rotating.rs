@@ -0,0 +1,226 @@
+use chrono::{NaiveDate, Utc};
+use std::sync::Arc;
+
+use super::ProviderCapabilities;
+use super::air_quality::AirQuality;
+use super::alerts::WeatherAlert;
+use super::error::{ProviderError, ProviderResult};
+use super::provider_trait::WeatherProvider;
+use super::providers_report::ProvidersReport;
+use crate::quota::{QuotaMeter, next_day};
+
+/// One provider instance bound to one pooled api key.
+pub struct KeyedProvider {
+    pub meter: Arc<QuotaMeter>,
+    pub provider: Box<dyn WeatherProvider>,
+}
+
+/// Tries each key of a pool in order, moving on when a key is rejected or out of quota.
+///
+/// In a pool of several keys, a rejected key is remembered in the usage file and skipped
+/// until its quota window resets: the `Retry-After` delay when the provider sent one, the next UTC day otherwise.
+pub struct RotatingProvider {
+    capabilities: ProviderCapabilities,
+    keys: Vec<KeyedProvider>,
+}
+
+impl RotatingProvider {
+    pub fn new(capabilities: ProviderCapabilities, keys: Vec<KeyedProvider>) -> Self {
+        Self { capabilities, keys }
+    }
+
+    /// Runs `call` with each key in turn until one is not rejected.
+    fn rotate<T>(
+        &self,
+        call: impl Fn(&dyn WeatherProvider) -> ProviderResult<T>,
+    ) -> ProviderResult<T> {
+        let mut last_err = None;
+        for key in &self.keys {
+            let err = match call(key.provider.as_ref()) {
+                Ok(value) => {
+                    key.meter.mark_served();
+                    return Ok(value);
+                }
+                Err(err) => err,
+            };
+            let now = Utc::now();
+            let until = match &err {
+                ProviderError::QuotaExceeded(failure) => failure
+                    .retry_after
+                    .map(|secs| now + chrono::Duration::seconds(secs as i64))
+                    .unwrap_or_else(|| next_day(now)),
+                ProviderError::Unauthorized(_) => next_day(now),
+                // local limits already hold this key back until they reset
+                ProviderError::LocalQuotaExceeded(_) => now,
+                _ => return Err(err),
+            };
+            // a lone key is retried every run, there is nothing to rotate to
+            if self.keys.len() > 1 {
+                if until > now {
+                    key.meter.mark_exhausted(until);
+                }
+                eprintln!(
+                    "warning: key '{}' unusable ({}), trying the next one",
+                    key.meter.label(),
+                    err
+                );
+            }
+            last_err = Some(err);
+        }
+        Err(last_err.unwrap_or(ProviderError::LocalQuotaExceeded(
+            "no api key configured".to_string(),
+        )))
+    }
+}
+
+impl WeatherProvider for RotatingProvider {
+    fn get_weather(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+    ) -> ProviderResult<ProvidersReport> {
+        self.rotate(|provider| provider.get_weather(latitude, longitude, date))
+    }
+
+    fn get_weather_range(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> ProviderResult<Vec<(NaiveDate, ProvidersReport)>> {
+        self.rotate(|provider| provider.get_weather_range(latitude, longitude, start, end))
+    }
+
+    fn get_alerts(&self, latitude: f64, longitude: f64) -> ProviderResult<Vec<WeatherAlert>> {
+        self.rotate(|provider| provider.get_alerts(latitude, longitude))
+    }
+
+    fn get_air_quality(&self, latitude: f64, longitude: f64) -> ProviderResult<AirQuality> {
+        self.rotate(|provider| provider.get_air_quality(latitude, longitude))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.capabilities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::error::HttpFailure;
+    use crate::providers::fixture::Fixture;
+    use crate::quota::{QuotaLimits, UsageFile, key_id};
+    use reqwest::header::HeaderMap;
+    use std::path::{Path, PathBuf};
+
+    struct Rejecting(u16);
+
+    impl WeatherProvider for Rejecting {
+        fn get_weather(&self, _: f64, _: f64, _: NaiveDate) -> ProviderResult<ProvidersReport> {
+            let failure = HttpFailure::new(self.0, &HeaderMap::new(), None, "no".to_string());
+            Err(ProviderError::from_http(failure))
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            Fixture::CAPABILITIES
+        }
+    }
+
+    fn keyed(path: &Path, label: &str, provider: Box<dyn WeatherProvider>) -> KeyedProvider {
+        KeyedProvider {
+            meter: Arc::new(QuotaMeter::with_path(
+                "ow-test",
+                label,
+                label,
+                QuotaLimits::default(),
+                path.to_path_buf(),
+            )),
+            provider,
+        }
+    }
+
+    fn usage_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "weather-rotating-{}-{}.toml",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+    }
+
+    #[test]
+    fn test_rotates_past_rejected_key() {
+        let path = usage_path("rotate");
+        let provider = RotatingProvider::new(
+            Fixture::CAPABILITIES,
+            vec![
+                keyed(&path, "first", Box::new(Rejecting(429))),
+                keyed(&path, "second", Box::new(Fixture::new(None, None))),
+            ],
+        );
+
+        assert!(provider.get_weather(50.45, 30.52, date()).is_ok());
+
+        let usage = UsageFile::load_from(&path);
+        let first = usage.usage("ow-test", &key_id("first"), Utc::now());
+        let second = usage.usage("ow-test", &key_id("second"), Utc::now());
+        assert!(first.exhausted_until.is_some());
+        assert!(first.last_served.is_none());
+        assert_eq!(second.label.as_deref(), Some("second"));
+        assert!(second.last_served.is_some());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_last_error_when_every_key_fails() {
+        let path = usage_path("all");
+        let provider = RotatingProvider::new(
+            Fixture::CAPABILITIES,
+            vec![
+                keyed(&path, "first", Box::new(Rejecting(429))),
+                keyed(&path, "second", Box::new(Rejecting(401))),
+            ],
+        );
+
+        assert!(matches!(
+            provider.get_weather(50.45, 30.52, date()),
+            Err(ProviderError::Unauthorized(_))
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_other_errors_do_not_rotate() {
+        let path = usage_path("other");
+        let provider = RotatingProvider::new(
+            Fixture::CAPABILITIES,
+            vec![
+                keyed(&path, "first", Box::new(Rejecting(500))),
+                keyed(&path, "second", Box::new(Fixture::new(None, None))),
+            ],
+        );
+
+        assert!(matches!(
+            provider.get_weather(50.45, 30.52, date()),
+            Err(ProviderError::ServerError(_))
+        ));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_single_key_is_not_remembered() {
+        let path = usage_path("single");
+        let provider = RotatingProvider::new(
+            Fixture::CAPABILITIES,
+            vec![keyed(&path, "only", Box::new(Rejecting(401)))],
+        );
+
+        assert!(provider.get_weather(50.45, 30.52, date()).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+/// Temperature unit of a provider's answer, converted to °C.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+/// Wind speed unit, converted to m/s.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeedUnit {
+    #[default]
+    Mps,
+    Kph,
+    Mph,
+    Knots,
+}
+
+/// Length unit of snowfall and visibility, converted to cm and km.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LengthUnit {
+    Mm,
+    Cm,
+    M,
+    Km,
+}
+
+/// Probability unit, converted to a 0-1 fraction.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FractionUnit {
+    #[default]
+    Fraction,
+    Percent,
+}
+
+impl TemperatureUnit {
+    pub(crate) fn to_celsius(self, value: f32) -> f32 {
+        match self {
+            Self::Celsius => value,
+            Self::Fahrenheit => (value - 32.) * 5. / 9.,
+            Self::Kelvin => value - 273.15,
+        }
+    }
+}
+
+impl SpeedUnit {
+    pub(crate) fn to_mps(self, value: f32) -> f32 {
+        match self {
+            Self::Mps => value,
+            Self::Kph => value / 3.6,
+            Self::Mph => value * 0.447_04,
+            Self::Knots => value * 0.514_444,
+        }
+    }
+}
+
+impl FractionUnit {
+    pub(crate) fn to_fraction(self, value: f32) -> f32 {
+        match self {
+            Self::Fraction => value,
+            Self::Percent => value / 100.,
+        }
+    }
+}
+
+impl LengthUnit {
+    pub(crate) fn to_cm(self, value: f32) -> f32 {
+        match self {
+            Self::Mm => value / 10.,
+            Self::Cm => value,
+            Self::M => value * 100.,
+            Self::Km => value * 100_000.,
+        }
+    }
+
+    pub(crate) fn to_km(self, value: f32) -> f32 {
+        match self {
+            Self::Mm => value / 1_000_000.,
+            Self::Cm => value / 100_000.,
+            Self::M => value / 1000.,
+            Self::Km => value,
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::NaiveDate;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use urlencoding::encode;
+
+use super::ProviderCapabilities;
+use super::alerts::WeatherAlert;
+use super::error::{HttpFailure, ProviderError, ProviderResult};
+use super::models::visual_crossing::VisualCrossingTimeline;
+use super::provider_trait::WeatherProvider;
+use super::providers_report::ProvidersReport;
+use crate::http::HttpClient;
+
+pub const DEFAULT_BASE_URL: &str =
+    "https://weather.visualcrossing.com/VisualCrossingWebServices/rest/services";
+
+/// Visual Crossing Timeline API: forecast, history and climate normals with one
+/// request shape, `/timeline/{location}/{start}/{end}`.
+pub struct VisualCrossing {
+    api_key: String,
+    base_url: String,
+    client: HttpClient,
+}
+
+impl VisualCrossing {
+    /// Model forecast for 15 days, observations back to 1970.
+    pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 15,
+        history_days: Some(55 * 365),
+        hourly: true,
+        alerts: true,
+        air_quality: false,
+        requires_key: true,
+    };
+
+    pub fn new(api_key: String, base_url: Option<String>, client: HttpClient) -> Self {
+        let base_url = base_url.unwrap_or(DEFAULT_BASE_URL.to_string());
+        Self {
+            api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }
+    }
+
+    fn prepare_url(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> String {
+        let location = format!("{latitude},{longitude}");
+        format!(
+            "{}/timeline/{}/{}/{}?unitGroup=metric&include=days,stats,alerts&contentType=json&key={}",
+            self.base_url,
+            encode(&location),
+            start,
+            end,
+            self.api_key
+        )
+    }
+
+    fn prepare_alerts_url(&self, latitude: f64, longitude: f64) -> String {
+        let location = format!("{latitude},{longitude}");
+        format!(
+            "{}/timeline/{}/today?unitGroup=metric&include=alerts&contentType=json&key={}",
+            self.base_url,
+            encode(&location),
+            self.api_key
+        )
+    }
+
+    fn fetch(&self, url: &str) -> ProviderResult<VisualCrossingTimeline> {
+        let response = self.client.get(url)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .map_err(|err| ProviderError::ParseError(err.without_url().to_string())),
+            status => {
+                let headers = response.headers().clone();
+                let body = response.text().unwrap_or_default();
+                Err(Self::parse_error(status.as_u16(), &headers, &body))
+            }
+        }
+    }
+
+    /// Errors are plain text, the status alone tells them apart.
+    fn parse_error(status: u16, headers: &HeaderMap, body: &str) -> ProviderError {
+        ProviderError::from_http(HttpFailure::new(
+            status,
+            headers,
+            None,
+            body.trim().to_string(),
+        ))
+    }
+}
+
+impl WeatherProvider for VisualCrossing {
+    fn get_weather(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+    ) -> ProviderResult<ProvidersReport> {
+        self.get_weather_range(latitude, longitude, date, date)?
+            .pop()
+            .map(|(_, report)| report)
+            .ok_or(ProviderError::DateIsOutOfRange(
+                "visual crossing".to_string(),
+            ))
+    }
+
+    /// One request for the whole range, each day is billed separately.
+    fn get_weather_range(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> ProviderResult<Vec<(NaiveDate, ProvidersReport)>> {
+        let body = self.fetch(&self.prepare_url(latitude, longitude, start, end))?;
+        let alerts: Vec<_> = body.alerts.into_iter().map(WeatherAlert::from).collect();
+        body.days
+            .into_iter()
+            .filter(|day| (start..=end).contains(&day.datetime))
+            .map(|day| {
+                let date = day.datetime;
+                let covering = alerts
+                    .iter()
+                    .filter(|alert| alert.covers(date))
+                    .cloned()
+                    .collect();
+                Ok((date, ProvidersReport::try_from(day)?.with_alerts(covering)))
+            })
+            .collect()
+    }
+
+    fn get_alerts(&self, latitude: f64, longitude: f64) -> ProviderResult<Vec<WeatherAlert>> {
+        let body = self.fetch(&self.prepare_alerts_url(latitude, longitude))?;
+        Ok(body.alerts.into_iter().map(WeatherAlert::from).collect())
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        Self::CAPABILITIES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpSettings;
+
+    fn provider() -> VisualCrossing {
+        let client = HttpClient::new(&HttpSettings::default()).unwrap();
+        VisualCrossing::new("vc_test_key".to_string(), None, client)
+    }
+
+    #[test]
+    fn test_prepare_url_for_range() {
+        let start = NaiveDate::from_ymd_opt(1995, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(1995, 12, 31).unwrap();
+        assert_eq!(
+            provider().prepare_url(51.5, -0.1, start, end),
+            "https://weather.visualcrossing.com/VisualCrossingWebServices/rest/services/timeline/\
+             51.5%2C-0.1/1995-01-01/1995-12-31?unitGroup=metric&include=days,stats,alerts&contentType=json\
+             &key=vc_test_key"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_plain_text() {
+        let err = VisualCrossing::parse_error(
+            429,
+            &HeaderMap::new(),
+            "You have exceeded the maximum number of daily result records for your account.\n",
+        );
+        assert!(matches!(
+            err,
+            ProviderError::QuotaExceeded(ref failure) if failure.message.ends_with("account.")
+        ));
+    }
+}
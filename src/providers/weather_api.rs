@@ -1,34 +1,135 @@
 use chrono::{Local, NaiveDate};
 use reqwest::StatusCode;
-use reqwest::blocking::Client;
-use reqwest::header::ACCEPT;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
 use urlencoding::encode;
 
-use super::error::{ProviderError, ProviderResult};
-use super::models::weather_api::{WeatherApiForecastError, WeatherApiReport};
+use super::ProviderCapabilities;
+use super::air_quality::AirQuality;
+use super::alerts::WeatherAlert;
+use super::error::{HttpFailure, ProviderError, ProviderResult};
+use super::models::weather_api::{
+    WeatherApiAlertsReport, WeatherApiCurrentReport, WeatherApiForecastError, WeatherApiReport,
+};
 use super::provider_trait::WeatherProvider;
 use super::providers_report::ProvidersReport;
+use crate::http::HttpClient;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.weatherapi.com/v1";
+
 pub struct WeatherApi {
     api_key: String,
+    base_url: String,
+    client: HttpClient,
 }
 
 impl WeatherApi {
-    pub fn new(api_key: String) -> Self {
-        Self { api_key }
+    /// `forecast.json` covers 14 days including today, `future.json` reaches 300 days out,
+    /// history goes back one year.
+    pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+        forecast_days: 300,
+        history_days: Some(365),
+        hourly: true,
+        alerts: true,
+        air_quality: true,
+        requires_key: true,
+    };
+
+    /// Last day offset from today served by `forecast.json`, further dates go to `future.json`.
+    const FORECAST_WINDOW: i64 = 13;
+
+    pub fn new(api_key: String, base_url: Option<String>, client: HttpClient) -> Self {
+        let base_url = base_url.unwrap_or(DEFAULT_BASE_URL.to_string());
+        Self {
+            api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+        }
     }
-    fn prepare_url(&self, latitude: f64, longitude: f64, date: NaiveDate) -> String {
+    fn prepare_url(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+        today: NaiveDate,
+    ) -> String {
         let location = format!("{latitude},{longitude}");
         let location_encoded = encode(&location);
-        let endpoint = if date >= Local::now().naive_local().date() {
-            "forecast.json"
+        let offset = (date - today).num_days();
+        if offset < 0 {
+            format!(
+                "{}/history.json?q={}&dt={}&key={}",
+                self.base_url, location_encoded, date, self.api_key
+            )
+        } else if offset <= Self::FORECAST_WINDOW {
+            format!(
+                "{}/forecast.json?q={}&days={}&alerts=yes&key={}",
+                self.base_url,
+                location_encoded,
+                offset + 1,
+                self.api_key
+            )
         } else {
-            "history.json"
-        };
+            format!(
+                "{}/future.json?q={}&dt={}&key={}",
+                self.base_url, location_encoded, date, self.api_key
+            )
+        }
+    }
+
+    fn prepare_air_quality_url(&self, latitude: f64, longitude: f64) -> String {
+        let location = format!("{latitude},{longitude}");
+        format!(
+            "{}/current.json?q={}&aqi=yes&key={}",
+            self.base_url,
+            encode(&location),
+            self.api_key
+        )
+    }
+
+    fn prepare_alerts_url(&self, latitude: f64, longitude: f64) -> String {
+        let location = format!("{latitude},{longitude}");
         format!(
-            "https://api.weatherapi.com/v1/{}?q={}&days=1&dt={}&key={}",
-            endpoint, location_encoded, date, self.api_key
+            "{}/alerts.json?q={}&key={}",
+            self.base_url,
+            encode(&location),
+            self.api_key
         )
     }
+
+    fn fetch<T: DeserializeOwned>(&self, url: &str) -> ProviderResult<T> {
+        let response = self.client.get(url)?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .map_err(|err| ProviderError::ParseError(err.without_url().to_string())),
+            status => {
+                let headers = response.headers().clone();
+                let body = response.text().unwrap_or_default();
+                Err(Self::parse_error(status.as_u16(), &headers, &body))
+            }
+        }
+    }
+
+    /// Classifies by WeatherAPI's error code first, its statuses are not always specific.
+    fn parse_error(status: u16, headers: &HeaderMap, body: &str) -> ProviderError {
+        let Ok(res) = serde_json::from_str::<WeatherApiForecastError>(body) else {
+            return ProviderError::from_http(HttpFailure::new(
+                status,
+                headers,
+                None,
+                body.to_string(),
+            ));
+        };
+        let code = res.error.code;
+        let failure = HttpFailure::new(status, headers, Some(code.to_string()), res.error.message);
+        match code {
+            1002 | 2006 | 2008 | 2009 => ProviderError::Unauthorized(failure),
+            2007 => ProviderError::QuotaExceeded(failure),
+            1006 => ProviderError::NotFound(failure),
+            _ => ProviderError::from_http(failure),
+        }
+    }
 }
 
 impl WeatherProvider for WeatherApi {
@@ -38,50 +139,118 @@ impl WeatherProvider for WeatherApi {
         longitude: f64,
         date: NaiveDate,
     ) -> ProviderResult<ProvidersReport> {
-        let client = Client::new();
-        let response = client
-            .get(self.prepare_url(latitude, longitude, date))
-            .header(ACCEPT, "application/json")
-            .send()
-            .map_err(|err| ProviderError::RequestFailed(err.to_string()))?;
-        match response.status() {
-            StatusCode::OK => {
-                let body: WeatherApiReport = response
-                    .json()
-                    .map_err(|err| ProviderError::ParseError(err.to_string()))?;
-                ProvidersReport::try_from(body)
-            }
-            _ => {
-                let res: WeatherApiForecastError = response
-                    .json()
-                    .map_err(|err| ProviderError::ParseError(err.to_string()))?;
-                Err(ProviderError::ProviderMsgError(res.message))
-            }
+        let today = Local::now().naive_local().date();
+        let mut body: WeatherApiReport =
+            self.fetch(&self.prepare_url(latitude, longitude, date, today))?;
+        body.forecast.forecastday.retain(|day| day.date == date);
+        if body.forecast.forecastday.is_empty() {
+            return Err(ProviderError::DateIsOutOfRange("weather api".to_string()));
         }
+        let alerts = std::mem::take(&mut body.alerts.alert)
+            .into_iter()
+            .map(WeatherAlert::from)
+            .filter(|alert| alert.covers(date))
+            .collect();
+        Ok(ProvidersReport::try_from(body)?.with_alerts(alerts))
+    }
+
+    fn get_alerts(&self, latitude: f64, longitude: f64) -> ProviderResult<Vec<WeatherAlert>> {
+        let body: WeatherApiAlertsReport =
+            self.fetch(&self.prepare_alerts_url(latitude, longitude))?;
+        Ok(body
+            .alerts
+            .alert
+            .into_iter()
+            .map(WeatherAlert::from)
+            .collect())
+    }
+
+    fn get_air_quality(&self, latitude: f64, longitude: f64) -> ProviderResult<AirQuality> {
+        let body: WeatherApiCurrentReport =
+            self.fetch(&self.prepare_air_quality_url(latitude, longitude))?;
+        Ok(AirQuality::from(body.current))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        Self::CAPABILITIES
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http::HttpSettings;
+
+    fn client() -> HttpClient {
+        HttpClient::new(&HttpSettings::default()).unwrap()
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+    }
 
     #[test]
-    fn test_prepare_url_for_future_date_uses_forecast() {
-        let provider = WeatherApi::new("test_key".to_string());
-        let future_date = NaiveDate::from_ymd_opt(9999, 1, 1).unwrap();
-        let url = provider.prepare_url(51.5, -0.1, future_date);
-        assert!(url.contains("forecast.json"));
+    fn test_prepare_url_for_today_uses_forecast() {
+        let provider = WeatherApi::new("test_key".to_string(), None, client());
+        let url = provider.prepare_url(51.5, -0.1, today(), today());
+        assert!(url.starts_with("https://api.weatherapi.com/v1/forecast.json"));
         assert!(url.contains("q=51.5%2C-0.1"));
-        assert!(url.contains("dt=9999-01-01"));
+        assert!(url.contains("days=1&"));
+    }
+
+    #[test]
+    fn test_prepare_url_requests_enough_forecast_days() {
+        let provider = WeatherApi::new("test_key".to_string(), None, client());
+        let date = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+        let url = provider.prepare_url(51.5, -0.1, date, today());
+        assert!(url.contains("forecast.json"));
+        assert!(url.contains("days=14&"));
+    }
+
+    #[test]
+    fn test_prepare_url_for_far_future_date_uses_future() {
+        let provider = WeatherApi::new("test_key".to_string(), None, client());
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let url = provider.prepare_url(51.5, -0.1, date, today());
+        assert!(url.contains("future.json"));
+        assert!(url.contains("dt=2025-01-15"));
     }
 
     #[test]
     fn test_prepare_url_for_historical_date_uses_history() {
-        let provider = WeatherApi::new("test_key".to_string());
+        let provider = WeatherApi::new("test_key".to_string(), None, client());
         // Use a date guaranteed to be in the past
         let past_date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
-        let url = provider.prepare_url(51.5, -0.1, past_date);
+        let url = provider.prepare_url(51.5, -0.1, past_date, today());
         assert!(url.contains("history.json"));
         assert!(url.contains("dt=2000-01-01"));
     }
+
+    #[test]
+    fn test_parse_error_quota_exceeded() {
+        let body =
+            r#"{"error":{"code":2007,"message":"API key has exceeded calls per month quota."}}"#;
+        let err = WeatherApi::parse_error(403, &HeaderMap::new(), body);
+        match err {
+            ProviderError::QuotaExceeded(failure) => {
+                assert_eq!(failure.status, 403);
+                assert_eq!(failure.code.as_deref(), Some("2007"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_invalid_key() {
+        let body = r#"{"error":{"code":2006,"message":"API key is invalid."}}"#;
+        let err = WeatherApi::parse_error(401, &HeaderMap::new(), body);
+        assert!(matches!(err, ProviderError::Unauthorized(_)));
+    }
+
+    #[test]
+    fn test_parse_error_location_not_found() {
+        let body = r#"{"error":{"code":1006,"message":"No matching location found."}}"#;
+        let err = WeatherApi::parse_error(400, &HeaderMap::new(), body);
+        assert!(matches!(err, ProviderError::NotFound(_)));
+    }
 }
@@ -0,0 +1,137 @@
+use std::fmt;
+
+const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// Arrows for the eight directions the wind blows towards, starting north.
+const ARROWS: [char; 8] = ['↑', '↗', '→', '↘', '↓', '↙', '←', '↖'];
+
+/// Upper bounds of Beaufort forces 0-11 in m/s, WMO scale.
+const BEAUFORT_LIMITS: [f32; 12] = [
+    0.5, 1.6, 3.4, 5.5, 8.0, 10.8, 13.9, 17.2, 20.8, 24.5, 28.5, 32.7,
+];
+
+const BEAUFORT_NAMES: [&str; 13] = [
+    "calm",
+    "light air",
+    "light breeze",
+    "gentle breeze",
+    "moderate breeze",
+    "fresh breeze",
+    "strong breeze",
+    "near gale",
+    "gale",
+    "strong gale",
+    "storm",
+    "violent storm",
+    "hurricane force",
+];
+
+/// Direction the wind blows from, in degrees clockwise from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindDirection(pub i32);
+
+impl WindDirection {
+    /// 16-point compass label such as `SW` or `NNE`.
+    pub fn compass_point(&self) -> &'static str {
+        COMPASS_POINTS[(self.0.rem_euclid(360) as f32 / 22.5).round() as usize % 16]
+    }
+
+    /// Arrow pointing where the wind goes, as on a weather map.
+    pub fn arrow(&self) -> char {
+        ARROWS[((self.0 + 180).rem_euclid(360) as f32 / 45.).round() as usize % 8]
+    }
+}
+
+impl fmt::Display for WindDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}° {}",
+            self.compass_point(),
+            self.0.rem_euclid(360),
+            self.arrow()
+        )
+    }
+}
+
+/// Beaufort force of a mean wind speed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Beaufort(pub u8);
+
+impl Beaufort {
+    pub fn from_speed(mps: f32) -> Self {
+        Self(
+            BEAUFORT_LIMITS
+                .iter()
+                .take_while(|limit| mps >= **limit)
+                .count() as u8,
+        )
+    }
+
+    pub fn description(&self) -> &'static str {
+        BEAUFORT_NAMES[(self.0 as usize).min(12)]
+    }
+}
+
+impl fmt::Display for Beaufort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Beaufort {}, {}", self.0, self.description())
+    }
+}
+
+/// Mean of directions in degrees weighted by wind speed, so calm hours barely count.
+/// `None` when nothing blows or the winds cancel out.
+pub fn weighted_direction(samples: impl Iterator<Item = (f32, f32)>) -> Option<i32> {
+    let (sin, cos) = samples.fold((0., 0.), |(sin, cos), (degrees, weight): (f32, f32)| {
+        let angle = degrees.to_radians();
+        (sin + weight * angle.sin(), cos + weight * angle.cos())
+    });
+    if sin.abs() < 1e-6 && cos.abs() < 1e-6 {
+        return None;
+    }
+    Some(sin.atan2(cos).to_degrees().rem_euclid(360.).round() as i32 % 360)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compass_point_and_arrow() {
+        let label = |deg| {
+            let direction = WindDirection(deg);
+            format!("{} {}", direction.compass_point(), direction.arrow())
+        };
+        assert_eq!(label(0), "N ↓");
+        assert_eq!(label(11), "N ↓");
+        assert_eq!(label(12), "NNE ↓");
+        assert_eq!(label(225), "SW ↗");
+        assert_eq!(label(250), "WSW →");
+        assert_eq!(label(349), "N ↓");
+        assert_eq!(label(-90), "W →");
+        assert_eq!(WindDirection(180).to_string(), "S 180° ↑");
+    }
+
+    #[test]
+    fn test_beaufort_bounds() {
+        assert_eq!(Beaufort::from_speed(0.), Beaufort(0));
+        assert_eq!(Beaufort::from_speed(5.4), Beaufort(3));
+        assert_eq!(Beaufort::from_speed(5.5), Beaufort(4));
+        assert_eq!(Beaufort::from_speed(40.), Beaufort(12));
+        assert_eq!(Beaufort(8).to_string(), "Beaufort 8, gale");
+    }
+
+    #[test]
+    fn test_weighted_direction_favours_strong_wind() {
+        // a calm hour from the east doesn't drag a steady westerly far
+        let samples = [(270., 10.), (270., 8.), (90., 1.)];
+        assert_eq!(weighted_direction(samples.into_iter()), Some(270));
+        // across north the mean wraps rather than averaging to south
+        let samples = [(350., 5.), (30., 5.)];
+        assert_eq!(weighted_direction(samples.into_iter()), Some(10));
+        assert_eq!(weighted_direction([(90., 0.)].into_iter()), None);
+    }
+}
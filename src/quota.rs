@@ -0,0 +1,486 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::argparser::ProviderKind;
+
+const USAGE_FILE_NAME: &str = "/tmp/weather_cli_usage.toml";
+/// Share of a daily or monthly limit after which every call warns.
+const WARN_RATIO: f32 = 0.8;
+const RATE_WINDOW_SECS: i64 = 60;
+
+#[derive(Error, Debug)]
+pub enum QuotaError {
+    #[error("local {window} limit of {limit} calls reached for {provider} key '{label}'")]
+    LimitReached {
+        provider: String,
+        label: String,
+        window: &'static str,
+        limit: u32,
+    },
+    #[error("{provider} key '{label}' is exhausted until {until}")]
+    Exhausted {
+        provider: String,
+        label: String,
+        until: DateTime<Utc>,
+    },
+}
+
+/// Call limits for one provider instance, from the `[quotas.<name>]` config table.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct QuotaLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_minute: Option<u32>,
+}
+
+impl QuotaLimits {
+    /// Free tier limits used when the config has none.
+    pub fn default_for(kind: ProviderKind) -> Self {
+        match kind {
+            // One Call 3.0 "pay as you call" includes 1000 calls a day
+            ProviderKind::OpenWeather => Self {
+                daily: Some(1000),
+                ..Default::default()
+            },
+            ProviderKind::WeatherApi
+            | ProviderKind::Fixture
+            | ProviderKind::Plugin
+            | ProviderKind::JsonApi
+            | ProviderKind::MetNorway
+            | ProviderKind::Nws
+            // billed per day of data, not per call
+            | ProviderKind::VisualCrossing => Self::default(),
+        }
+    }
+}
+
+/// Calls made with one key, windows are UTC days and months.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Usage {
+    pub day: Option<NaiveDate>,
+    pub day_calls: u32,
+    pub month: Option<String>,
+    pub month_calls: u32,
+    /// Unix seconds of calls inside the rate limit window.
+    #[serde(default)]
+    pub recent: Vec<i64>,
+    /// Config label of the key, its value is never stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Set when the provider rejected the key or its quota, cleared once passed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exhausted_until: Option<DateTime<Utc>>,
+    /// Last time this key answered a weather request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_served: Option<DateTime<Utc>>,
+}
+
+impl Usage {
+    /// Drops counters whose window has passed.
+    fn roll(&mut self, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.day_calls = 0;
+        }
+        let month = month_key(today);
+        if self.month.as_deref() != Some(month.as_str()) {
+            self.month = Some(month);
+            self.month_calls = 0;
+        }
+        self.recent
+            .retain(|&ts| now.timestamp() - ts < RATE_WINDOW_SECS);
+        if self.exhausted_until.is_some_and(|until| until <= now) {
+            self.exhausted_until = None;
+        }
+    }
+
+    /// How long to wait for a rate limit slot, or which window is used up.
+    fn check(
+        &self,
+        limits: &QuotaLimits,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Duration>, (&'static str, u32)> {
+        if let Some(limit) = limits.daily.filter(|&limit| self.day_calls >= limit) {
+            return Err(("daily", limit));
+        }
+        if let Some(limit) = limits.monthly.filter(|&limit| self.month_calls >= limit) {
+            return Err(("monthly", limit));
+        }
+        Ok(limits
+            .per_minute
+            .filter(|&limit| self.recent.len() >= limit as usize)
+            .and_then(|_| self.recent.first())
+            .map(|&oldest| {
+                let left = RATE_WINDOW_SECS - (now.timestamp() - oldest);
+                Duration::from_secs(left.max(1) as u64)
+            }))
+    }
+
+    fn record(&mut self, now: DateTime<Utc>) {
+        self.day_calls += 1;
+        self.month_calls += 1;
+        self.recent.push(now.timestamp());
+    }
+
+    fn near_limit(&self, limits: &QuotaLimits) -> Option<(&'static str, u32, u32)> {
+        let near = |used: u32, limit: u32| used as f32 >= limit as f32 * WARN_RATIO;
+        if let Some(limit) = limits.daily.filter(|&limit| near(self.day_calls, limit)) {
+            return Some(("daily", self.day_calls, limit));
+        }
+        limits
+            .monthly
+            .filter(|&limit| near(self.month_calls, limit))
+            .map(|limit| ("monthly", self.month_calls, limit))
+    }
+}
+
+/// Persisted usage for every provider instance and key, keyed by `<instance>:<key id>`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct UsageFile {
+    #[serde(default)]
+    pub keys: BTreeMap<String, Usage>,
+}
+
+impl UsageFile {
+    pub fn load() -> Self {
+        Self::load_from(&PathBuf::from(USAGE_FILE_NAME))
+    }
+
+    pub(crate) fn load_from(path: &PathBuf) -> Self {
+        fs::read_to_string(path)
+            .map(|contents| toml::from_str::<Self>(&contents).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    fn dump(&self, path: &PathBuf) {
+        // usage tracking must never break a weather request
+        let _ = fs::write(path, toml::to_string(self).unwrap_or_default());
+    }
+
+    /// Usage of `provider` with `key_id` as of `now`.
+    pub fn usage(&self, provider: &str, key_id: &str, now: DateTime<Utc>) -> Usage {
+        let mut usage = self
+            .keys
+            .get(&usage_key(provider, key_id))
+            .cloned()
+            .unwrap_or_default();
+        usage.roll(now);
+        usage
+    }
+}
+
+/// Counts and limits the calls one provider key makes.
+#[derive(Debug)]
+pub struct QuotaMeter {
+    provider: String,
+    label: String,
+    key_id: String,
+    limits: QuotaLimits,
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl QuotaMeter {
+    pub fn new(provider: &str, label: &str, api_key: &str, limits: QuotaLimits) -> Self {
+        Self::with_path(
+            provider,
+            label,
+            api_key,
+            limits,
+            PathBuf::from(USAGE_FILE_NAME),
+        )
+    }
+
+    pub fn with_path(
+        provider: &str,
+        label: &str,
+        api_key: &str,
+        limits: QuotaLimits,
+        path: PathBuf,
+    ) -> Self {
+        Self {
+            provider: provider.to_string(),
+            label: label.to_string(),
+            key_id: key_id(api_key),
+            limits,
+            path,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Books one call, waiting for the rate limit when needed.
+    pub fn acquire(&self) -> Result<(), QuotaError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            let now = Utc::now();
+            let file = UsageFile::load_from(&self.path);
+            let mut usage = file.usage(&self.provider, &self.key_id, now);
+            if let Some(until) = usage.exhausted_until {
+                return Err(QuotaError::Exhausted {
+                    provider: self.provider.clone(),
+                    label: self.label.clone(),
+                    until,
+                });
+            }
+            match usage.check(&self.limits, now) {
+                Err((window, limit)) => {
+                    return Err(QuotaError::LimitReached {
+                        provider: self.provider.clone(),
+                        label: self.label.clone(),
+                        window,
+                        limit,
+                    });
+                }
+                Ok(Some(wait)) => {
+                    eprintln!(
+                        "warning: {} rate limit of {} calls/min reached, waiting {}s",
+                        self.provider,
+                        self.limits.per_minute.unwrap_or_default(),
+                        wait.as_secs()
+                    );
+                    sleep(wait);
+                }
+                Ok(None) => {
+                    usage.record(now);
+                    if let Some((window, used, limit)) = usage.near_limit(&self.limits) {
+                        eprintln!(
+                            "warning: {} key '{}' used {} of {} {} calls",
+                            self.provider, self.label, used, limit, window
+                        );
+                    }
+                    self.store(file, usage);
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Skips this key until `until`, across runs.
+    pub fn mark_exhausted(&self, until: DateTime<Utc>) {
+        self.update(|usage| usage.exhausted_until = Some(until));
+    }
+
+    /// Notes that this key answered a weather request.
+    pub fn mark_served(&self) {
+        self.update(|usage| usage.last_served = Some(Utc::now()));
+    }
+
+    fn update(&self, change: impl FnOnce(&mut Usage)) {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let file = UsageFile::load_from(&self.path);
+        let mut usage = file.usage(&self.provider, &self.key_id, Utc::now());
+        change(&mut usage);
+        self.store(file, usage);
+    }
+
+    fn store(&self, mut file: UsageFile, mut usage: Usage) {
+        usage.label = Some(self.label.clone());
+        file.keys
+            .insert(usage_key(&self.provider, &self.key_id), usage);
+        file.dump(&self.path);
+    }
+}
+
+/// Start of the next UTC day, when daily provider quotas reset.
+pub fn next_day(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.date_naive()
+        .checked_add_days(Days::new(1))
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc())
+        .unwrap_or(now)
+}
+
+/// Stable, non-reversible id of an api key (FNV-1a), safe to persist and print.
+pub fn key_id(api_key: &str) -> String {
+    let hash = api_key
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("key-{:08x}", hash as u32)
+}
+
+fn usage_key(provider: &str, key_id: &str) -> String {
+    format!("{}:{}", provider, key_id)
+}
+
+fn month_key(date: NaiveDate) -> String {
+    format!("{:04}-{:02}", date.year(), date.month())
+}
+
+/// One line of `weather quota`.
+pub struct QuotaReport<'a> {
+    pub provider: &'a str,
+    pub label: &'a str,
+    pub key_id: &'a str,
+    pub usage: &'a Usage,
+    pub limits: &'a QuotaLimits,
+}
+
+impl fmt::Display for QuotaReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = |limit: Option<u32>| limit.map(|l| l.to_string()).unwrap_or("-".to_string());
+        write!(
+            f,
+            "{} {} ({}): today {}/{} | month {}/{} | rate limit {}/min",
+            self.provider,
+            self.label,
+            self.key_id,
+            self.usage.day_calls,
+            limit(self.limits.daily),
+            self.usage.month_calls,
+            limit(self.limits.monthly),
+            limit(self.limits.per_minute),
+        )?;
+        if let Some((window, _, _)) = self.usage.near_limit(self.limits) {
+            write!(f, " (near {} limit)", window)?;
+        }
+        if let Some(until) = self.usage.exhausted_until {
+            write!(
+                f,
+                " (exhausted until {})",
+                until.format("%Y-%m-%d %H:%M UTC")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, secs: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, day, 12, 0, secs).unwrap()
+    }
+
+    #[test]
+    fn test_daily_limit() {
+        let limits = QuotaLimits {
+            daily: Some(2),
+            ..Default::default()
+        };
+        let mut usage = Usage::default();
+        usage.roll(at(1, 0));
+        usage.record(at(1, 0));
+        assert_eq!(usage.check(&limits, at(1, 0)), Ok(None));
+        usage.record(at(1, 1));
+        assert_eq!(usage.check(&limits, at(1, 1)), Err(("daily", 2)));
+    }
+
+    #[test]
+    fn test_windows_roll_over() {
+        let mut usage = Usage::default();
+        usage.roll(at(1, 0));
+        usage.record(at(1, 0));
+        usage.roll(at(2, 0));
+        assert_eq!(usage.day_calls, 0);
+        assert_eq!(usage.month_calls, 1);
+        usage.roll(Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(usage.month_calls, 0);
+    }
+
+    #[test]
+    fn test_rate_limit_waits_for_oldest_call() {
+        let limits = QuotaLimits {
+            per_minute: Some(2),
+            ..Default::default()
+        };
+        let mut usage = Usage::default();
+        usage.roll(at(1, 0));
+        usage.record(at(1, 0));
+        usage.record(at(1, 20));
+        assert_eq!(
+            usage.check(&limits, at(1, 20)),
+            Ok(Some(Duration::from_secs(40)))
+        );
+        usage.roll(at(1, 0) + chrono::Duration::seconds(61));
+        assert_eq!(usage.check(&limits, at(1, 0)), Ok(None));
+    }
+
+    #[test]
+    fn test_near_limit() {
+        let limits = QuotaLimits {
+            daily: Some(10),
+            ..Default::default()
+        };
+        let usage = Usage {
+            day_calls: 8,
+            ..Default::default()
+        };
+        assert_eq!(usage.near_limit(&limits), Some(("daily", 8, 10)));
+    }
+
+    #[test]
+    fn test_key_id_hides_key() {
+        let id = key_id("0123456789abcdef");
+        assert!(id.starts_with("key-"));
+        assert!(!id.contains("0123456789abcdef"));
+        assert_eq!(id, key_id("0123456789abcdef"));
+    }
+
+    #[test]
+    fn test_meter_persists_and_enforces() {
+        let path = std::env::temp_dir().join(format!("weather-usage-{}.toml", std::process::id()));
+        let limits = QuotaLimits {
+            daily: Some(1),
+            ..Default::default()
+        };
+        let meter = QuotaMeter::with_path("OpenWeather", "default", "k", limits, path.clone());
+        assert!(meter.acquire().is_ok());
+        assert!(matches!(
+            meter.acquire(),
+            Err(QuotaError::LimitReached {
+                window: "daily",
+                ..
+            })
+        ));
+        let usage = UsageFile::load_from(&path).usage("OpenWeather", &key_id("k"), Utc::now());
+        assert_eq!(usage.day_calls, 1);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_exhausted_key_is_refused_until_reset() {
+        let path =
+            std::env::temp_dir().join(format!("weather-exhausted-{}.toml", std::process::id()));
+        let meter = QuotaMeter::with_path(
+            "WeatherApi",
+            "team-b",
+            "k2",
+            QuotaLimits::default(),
+            path.clone(),
+        );
+        meter.mark_exhausted(next_day(Utc::now()));
+        let err = meter.acquire().unwrap_err();
+        assert!(err.to_string().contains("'team-b'"));
+        meter.mark_exhausted(Utc::now() - chrono::Duration::seconds(1));
+        assert!(meter.acquire().is_ok());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_next_day() {
+        assert_eq!(
+            next_day(at(1, 30)),
+            Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap()
+        );
+    }
+}
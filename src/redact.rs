@@ -0,0 +1,87 @@
+use std::sync::RwLock;
+
+pub const REDACTED: &str = "***";
+
+/// Query parameters that carry credentials in provider URLs.
+const SECRET_PARAMS: [&str; 5] = ["appid", "key", "api_key", "apikey", "token"];
+
+static SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Remembers a secret so that every later `redact` call scrubs it.
+pub fn register_secret(secret: &str) {
+    if secret.is_empty() {
+        return;
+    }
+    let mut secrets = SECRETS.write().unwrap_or_else(|e| e.into_inner());
+    if !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+    }
+}
+
+/// Replaces registered secrets and credential query parameters with `***`.
+pub fn redact(text: &str) -> String {
+    let mut out = scrub_query_params(text);
+    let secrets = SECRETS.read().unwrap_or_else(|e| e.into_inner());
+    for secret in secrets.iter() {
+        out = out.replace(secret.as_str(), REDACTED);
+    }
+    out
+}
+
+fn scrub_query_params(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(['?', '&']) {
+        let (head, tail) = rest.split_at(pos + 1);
+        out.push_str(head);
+        rest = tail;
+        let Some(name) = SECRET_PARAMS
+            .iter()
+            .find(|name| rest.starts_with(&format!("{name}=")))
+        else {
+            continue;
+        };
+        out.push_str(name);
+        out.push('=');
+        out.push_str(REDACTED);
+        let value = &rest[name.len() + 1..];
+        let end = value
+            .find(|c: char| c == '&' || c == ')' || c == '"' || c.is_whitespace())
+            .unwrap_or(value.len());
+        rest = &value[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_query_params() {
+        let text = "error sending request for url (https://api.weatherapi.com/v1/forecast.json?q=1%2C2&days=1&key=abc123)";
+        assert_eq!(
+            redact(text),
+            "error sending request for url (https://api.weatherapi.com/v1/forecast.json?q=1%2C2&days=1&key=***)"
+        );
+    }
+
+    #[test]
+    fn test_scrub_keeps_similar_params() {
+        let text = "https://host/path?monkey=1&appid=xyz&units=metric";
+        assert_eq!(
+            redact(text),
+            "https://host/path?monkey=1&appid=***&units=metric"
+        );
+    }
+
+    #[test]
+    fn test_registered_secret_is_redacted_anywhere() {
+        register_secret("s3cr3t-value");
+        assert_eq!(
+            redact("invalid key s3cr3t-value given"),
+            "invalid key *** given"
+        );
+    }
+}
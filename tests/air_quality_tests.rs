@@ -0,0 +1,154 @@
+mod common;
+
+use std::collections::HashMap;
+use std::time::Duration;
+use weather::argparser::AirSource;
+use weather::cli::show_air;
+use weather::configs::{AirQualityConfig, Config, ProviderConfig};
+use weather::http::{HttpClient, HttpSettings};
+use weather::providers::WeatherProvider;
+use weather::providers::air_quality::{AqiScale, Pollutant};
+use weather::providers::open_weather::OpenWeather;
+
+const OPEN_METEO: &str = r#"{"latitude":52.52,"longitude":13.42,"current_units":{},
+    "current":{"time":1751374800,"interval":3600,"pm10":21.3,"pm2_5":12.4,
+    "carbon_monoxide":182.0,"nitrogen_dioxide":14.9,"sulphur_dioxide":2.1,"ozone":96.0,
+    "alder_pollen":0.0,"birch_pollen":null,"grass_pollen":31.5,"mugwort_pollen":0.4,
+    "olive_pollen":null,"ragweed_pollen":null}}"#;
+
+fn config(provider: Option<(&str, ProviderConfig)>, air_quality_url: Option<String>) -> Config {
+    Config {
+        default_provider: provider.as_ref().map(|(name, _)| name.to_string()),
+        providers: provider
+            .map(|(name, config)| HashMap::from([(name.to_string(), config)]))
+            .unwrap_or_default(),
+        air_quality: AirQualityConfig {
+            base_url: air_quality_url,
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_open_meteo_with_pollen() {
+    let (base_url, requests) = common::serve(vec![(200, OPEN_METEO.to_string())]);
+
+    let air = show_air(
+        config(None, Some(base_url)),
+        Some(52.52),
+        Some(13.41),
+        None,
+        None,
+        Some(AirSource::OpenMeteo),
+        HttpSettings::default(),
+    )
+    .unwrap();
+
+    assert_eq!(air.source, "Open-Meteo");
+    assert_eq!(air.pm2_5, Some(12.4));
+    assert_eq!(
+        air.pollen,
+        vec![
+            ("alder".to_string(), 0.0),
+            ("grass".to_string(), 31.5),
+            ("mugwort".to_string(), 0.4)
+        ]
+    );
+    let us = air.us_aqi().unwrap();
+    assert_eq!((us.value, us.pollutant), (57, Pollutant::Pm2_5));
+    let eu = air.eu_aqi().unwrap();
+    assert_eq!((eu.scale, eu.value), (AqiScale::European, 2));
+    let text = air.to_string();
+    assert!(
+        text.contains("US AQI        : 57 Moderate (PM2.5)"),
+        "{text}"
+    );
+    assert!(
+        text.contains("Pollen        : alder 0 | grass 32 | mugwort 0 grains/m³"),
+        "{text}"
+    );
+    let target = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert!(
+        target.starts_with("/air-quality?latitude=52.52&longitude=13.41&current="),
+        "{target}"
+    );
+}
+
+#[test]
+fn test_selected_provider_answers_when_capable() {
+    let body = r#"{"location":{"name":"London"},"current":{"last_updated_epoch":1751374800,
+        "temp_c":21.0,"air_quality":{"co":230.3,"no2":13.2,"o3":72.0,"so2":3.4,"pm2_5":8.1,
+        "pm10":10.2,"us-epa-index":1,"gb-defra-index":1}}}"#;
+    let (base_url, requests) = common::serve(vec![(200, body.to_string())]);
+    let weather_api = ProviderConfig::WeatherApi {
+        api_key: "wa-air-key".to_string(),
+        api_keys: Vec::new(),
+        base_url: Some(base_url),
+    };
+
+    let air = show_air(
+        config(Some(("wa", weather_api)), None),
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        None,
+        HttpSettings::default(),
+    )
+    .unwrap();
+
+    assert_eq!(air.source, "WeatherAPI");
+    assert_eq!(air.us_aqi().unwrap().value, 45);
+    assert!(
+        requests
+            .recv_timeout(Duration::from_secs(1))
+            .unwrap()
+            .starts_with("/current.json?q=51.5%2C-0.1&aqi=yes&key=")
+    );
+}
+
+#[test]
+fn test_falls_back_to_open_meteo() {
+    let (base_url, requests) = common::serve(vec![(200, OPEN_METEO.to_string())]);
+    let fixture = ProviderConfig::Fixture {
+        path: None,
+        seed: None,
+    };
+
+    let air = show_air(
+        config(Some(("offline", fixture)), Some(base_url)),
+        Some(52.52),
+        Some(13.41),
+        None,
+        None,
+        None,
+        HttpSettings::default(),
+    )
+    .unwrap();
+
+    assert_eq!(air.source, "Open-Meteo");
+    assert!(requests.recv_timeout(Duration::from_secs(1)).is_ok());
+}
+
+#[test]
+fn test_open_weather_air_pollution() {
+    let body = r#"{"coord":{"lon":30.52,"lat":50.45},"list":[{"main":{"aqi":2},
+        "components":{"co":201.94,"no":0.02,"no2":0.77,"o3":68.66,"so2":0.64,"pm2_5":0.5,
+        "pm10":0.54,"nh3":0.12},"dt":1751374800}]}"#;
+    let (base_url, requests) = common::serve(vec![(200, body.to_string())]);
+    let client = HttpClient::new(&HttpSettings::default()).unwrap();
+    let provider = OpenWeather::new("ow-air-key".to_string(), Some(base_url), client);
+
+    let air = provider.get_air_quality(50.45, 30.52).unwrap();
+
+    assert_eq!(air.source, "OpenWeather");
+    assert_eq!(air.o3, Some(68.66));
+    let eu = air.eu_aqi().unwrap();
+    assert_eq!((eu.value, eu.pollutant), (2, Pollutant::O3));
+    assert!(
+        requests
+            .recv_timeout(Duration::from_secs(1))
+            .unwrap()
+            .starts_with("/air_pollution?lat=50.45&lon=30.52&appid=")
+    );
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use chrono::{Days, Local, NaiveDate};
+use std::collections::HashMap;
+use std::time::Duration;
+use weather::cli::{WeatherCliError, check_alerts, run, show_alerts};
+use weather::configs::{Config, ProviderConfig};
+use weather::http::HttpSettings;
+use weather::providers::alerts::AlertSeverity;
+use weather::providers::error::ProviderError;
+
+fn today() -> NaiveDate {
+    Local::now().naive_local().date()
+}
+
+fn config(name: &str, provider_config: ProviderConfig) -> Config {
+    Config {
+        default_provider: Some(name.to_string()),
+        providers: HashMap::from([(name.to_string(), provider_config)]),
+        ..Default::default()
+    }
+}
+
+fn weather_api(base_url: String) -> Config {
+    config(
+        "wa",
+        ProviderConfig::WeatherApi {
+            api_key: "wa-alerts-key".to_string(),
+            api_keys: Vec::new(),
+            base_url: Some(base_url),
+        },
+    )
+}
+
+#[test]
+fn test_forecast_alerts_above_report() {
+    let tomorrow = today().checked_add_days(Days::new(1)).unwrap();
+    let body = format!(
+        r#"{{"forecast":{{"forecastday":[{{"date":"{today}","day":{{
+            "maxtemp_c":31.0,"mintemp_c":22.0,"avgtemp_c":26.0,"maxwind_kph":18.0,
+            "avghumidity":40.0,"daily_chance_of_rain":0.0,"condition":{{"text":"Sunny"}}}}}}]}},
+        "alerts":{{"alert":[{{"headline":"Met Office: Yellow warning for extreme heat",
+            "severity":"Moderate","event":"Yellow warning for extreme heat",
+            "effective":"{today}T10:00:00+01:00","expires":"{tomorrow}T23:59:00+01:00",
+            "desc":"Temperatures up to 33C."}}]}}}}"#,
+        today = today()
+    );
+    let (base_url, requests) = common::serve(vec![(200, body)]);
+
+    let alerts = run(
+        weather_api(base_url),
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        today(),
+        HttpSettings::default(),
+    )
+    .unwrap();
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].event, "Yellow warning for extreme heat");
+    assert_eq!(alerts[0].severity, AlertSeverity::Moderate);
+    let target = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert!(target.contains("&alerts=yes&"), "{target}");
+
+    let err = check_alerts(&alerts, Some(AlertSeverity::Unknown)).unwrap_err();
+    assert!(matches!(
+        err,
+        WeatherCliError::ActiveAlerts(1, AlertSeverity::Unknown)
+    ));
+    assert_eq!(err.exit_code(), 9);
+    assert!(check_alerts(&alerts, Some(AlertSeverity::Severe)).is_ok());
+    assert!(check_alerts(&alerts, None).is_ok());
+}
+
+#[test]
+fn test_alerts_command_open_weather() {
+    let body = r#"{"lat":50.45,"lon":30.52,"timezone":"Europe/Kyiv","alerts":[
+        {"sender_name":"Ukrainian Hydrometeorological Center","event":"Strong wind",
+         "start":1737655200,"end":1737756000,"description":"Gusts 15-20 m/s.","tags":["Wind"]}]}"#;
+    let (base_url, requests) = common::serve(vec![(200, body.to_string())]);
+    let config = config(
+        "ow",
+        ProviderConfig::OpenWeather {
+            api_key: "ow-alerts-key".to_string(),
+            api_keys: Vec::new(),
+            base_url: Some(base_url),
+        },
+    );
+
+    let alerts = show_alerts(
+        config,
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        HttpSettings::default(),
+    )
+    .unwrap();
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(
+        alerts[0].sender.as_deref(),
+        Some("Ukrainian Hydrometeorological Center")
+    );
+    assert_eq!(alerts[0].severity, AlertSeverity::Unknown);
+    assert!(
+        alerts[0]
+            .to_string()
+            .contains("2025-01-23 18:00 +00:00 to 2025-01-24 22:00 +00:00")
+    );
+    let target = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert!(
+        target.ends_with("&exclude=current,minutely,hourly,daily"),
+        "{target}"
+    );
+}
+
+#[test]
+fn test_alerts_command_weather_api_none() {
+    let body = r#"{"location":{"name":"London"},"alerts":{"alert":[]}}"#;
+    let (base_url, requests) = common::serve(vec![(200, body.to_string())]);
+
+    let alerts = show_alerts(
+        weather_api(base_url),
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        HttpSettings::default(),
+    )
+    .unwrap();
+
+    assert!(alerts.is_empty());
+    assert!(
+        requests
+            .recv_timeout(Duration::from_secs(1))
+            .unwrap()
+            .starts_with("/alerts.json?q=51.5%2C-0.1&key=")
+    );
+}
+
+#[test]
+fn test_alerts_command_unsupported() {
+    let config = config(
+        "offline",
+        ProviderConfig::Fixture {
+            path: None,
+            seed: None,
+        },
+    );
+
+    let err = show_alerts(
+        config,
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        HttpSettings::default(),
+    )
+    .unwrap_err();
+
+    assert!(matches!(
+        err,
+        WeatherCliError::ProviderError(ProviderError::Unsupported(_))
+    ));
+    assert_eq!(err.exit_code(), 10);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use chrono::Local;
+use weather::geocoder::Geocoder;
+use weather::http::{HttpClient, HttpSettings};
+use weather::providers::WeatherProvider;
+use weather::providers::error::ProviderError;
+use weather::providers::open_weather::OpenWeather;
+use weather::providers::weather_api::WeatherApi;
+
+fn client() -> HttpClient {
+    HttpClient::new(&HttpSettings {
+        retries: Some(0),
+        ..Default::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_weather_api_uses_base_url() {
+    let today = Local::now().naive_local().date();
+    let body = format!(
+        r#"{{"forecast":{{"forecastday":[{{"date":"{today}","day":{{
+            "maxtemp_c":25.0,"mintemp_c":15.0,"avgtemp_c":20.0,"maxwind_kph":36.0,
+            "avghumidity":60.0,"daily_chance_of_rain":10.0,"condition":{{"text":"Sunny"}}}}}}]}}}}"#
+    );
+    let (base_url, requests) = common::serve(vec![(200, body)]);
+    let provider = WeatherApi::new("wa_key".to_string(), Some(base_url), client());
+
+    let report = provider.get_weather(51.5, -0.1, today).unwrap();
+
+    assert!(report.to_string().contains("Sunny"));
+    assert!(
+        requests
+            .recv()
+            .unwrap()
+            .starts_with("/forecast.json?q=51.5%2C-0.1&days=1")
+    );
+}
+
+#[test]
+fn test_open_weather_error_from_base_url() {
+    let today = Local::now().naive_local().date();
+    let (base_url, requests) = common::serve(vec![(
+        401,
+        r#"{"cod":401,"message":"Invalid API key."}"#.to_string(),
+    )]);
+    let provider = OpenWeather::new("ow_key".to_string(), Some(base_url), client());
+
+    let err = provider.get_weather(40.71, -74.01, today).unwrap_err();
+
+    assert!(matches!(err, ProviderError::Unauthorized(_)));
+    assert!(requests.recv().unwrap().starts_with("/onecall?lat=40.71"));
+}
+
+#[test]
+fn test_geocoder_uses_base_url() {
+    let body = r#"{"results":[{"name":"Kyiv","latitude":50.45,"longitude":30.52,
+        "population":2797553,"country":"Ukraine","admin1":"Kyiv City"}]}"#;
+    let (base_url, requests) = common::serve(vec![(200, body.to_string())]);
+    let geocoder = Geocoder::new(Some(format!("{base_url}/")), client());
+
+    let result = geocoder.resolve_address("Kyiv".to_string(), None).unwrap();
+
+    assert_eq!(result.results.len(), 1);
+    assert_eq!(result.results[0].latitude, 50.45);
+    assert!(requests.recv().unwrap().starts_with("/search?name=Kyiv"));
+}
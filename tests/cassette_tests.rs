@@ -0,0 +1,230 @@
+mod common;
+
+use chrono::{Days, Local, NaiveDate};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use weather::argparser::ProviderKind;
+use weather::cli::{WeatherCliError, run};
+use weather::configs::{Config, ProviderConfig};
+use weather::http::HttpSettings;
+use weather::providers::error::ProviderError;
+use weather::redact;
+
+fn cassette(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/cassettes")
+        .join(name)
+}
+
+fn config(provider: ProviderKind, base_url: Option<String>) -> Config {
+    let provider_config = match provider {
+        ProviderKind::OpenWeather => ProviderConfig::OpenWeather {
+            api_key: "ow-cassette-key".to_string(),
+            api_keys: Vec::new(),
+            base_url,
+        },
+        ProviderKind::WeatherApi => ProviderConfig::WeatherApi {
+            api_key: "wa-cassette-key".to_string(),
+            api_keys: Vec::new(),
+            base_url,
+        },
+        ProviderKind::Fixture | ProviderKind::Plugin => {
+            unreachable!("{provider} provider makes no http requests")
+        }
+        ProviderKind::JsonApi
+        | ProviderKind::MetNorway
+        | ProviderKind::Nws
+        | ProviderKind::VisualCrossing => {
+            unreachable!("no cassettes are recorded for {provider}")
+        }
+    };
+    Config {
+        default_provider: Some(provider.to_string()),
+        providers: HashMap::from([(provider.to_string(), provider_config)]),
+        ..Default::default()
+    }
+}
+
+fn replay(name: &str) -> HttpSettings {
+    HttpSettings {
+        replay: Some(cassette(name)),
+        ..Default::default()
+    }
+}
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2023, 5, 1).unwrap()
+}
+
+/// A day inside WeatherAPI's year of history whenever the tests run.
+fn recent() -> NaiveDate {
+    Local::now().naive_local().date() - Days::new(30)
+}
+
+/// Replays `name` with its recorded day moved to `day`, WeatherAPI reports are kept only
+/// for the requested day.
+fn replay_on(name: &str, day: NaiveDate) -> HttpSettings {
+    let contents = fs::read_to_string(cassette(name).join("cassette.json")).unwrap();
+    let dir = std::env::temp_dir().join(format!("weather-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("cassette.json"),
+        contents.replace(&date().to_string(), &day.to_string()),
+    )
+    .unwrap();
+    replay_dir(dir)
+}
+
+#[test]
+fn test_open_weather_history_by_toponym() {
+    let result = run(
+        config(ProviderKind::OpenWeather, None),
+        None,
+        None,
+        Some("Kyiv".to_string()),
+        None,
+        date(),
+        replay("openweather_history_kyiv"),
+    );
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_open_weather_invalid_key() {
+    let err = run(
+        config(ProviderKind::OpenWeather, None),
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        date(),
+        replay("openweather_invalid_key"),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        WeatherCliError::ProviderError(ProviderError::Unauthorized(_))
+    ));
+    assert_eq!(err.exit_code(), 4);
+}
+
+#[test]
+fn test_weather_api_history_by_toponym() {
+    let result = run(
+        config(ProviderKind::WeatherApi, None),
+        None,
+        None,
+        Some("London".to_string()),
+        None,
+        recent(),
+        replay_on("weatherapi_history_london", recent()),
+    );
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_weather_api_quota_exceeded() {
+    let err = run(
+        config(ProviderKind::WeatherApi, None),
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        recent(),
+        replay("weatherapi_quota_exceeded"),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        WeatherCliError::ProviderError(ProviderError::QuotaExceeded(ref f)) if f.code.as_deref() == Some("2007")
+    ));
+    assert_eq!(err.exit_code(), 5);
+}
+
+#[test]
+fn test_weather_api_server_error_after_retries() {
+    let err = run(
+        config(ProviderKind::WeatherApi, None),
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        recent(),
+        replay("weatherapi_server_error"),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        WeatherCliError::ProviderError(ProviderError::ServerError(ref f)) if f.retry_after == Some(1)
+    ));
+    assert_eq!(err.exit_code(), 8);
+}
+
+#[test]
+fn test_missing_interaction_fails_without_network() {
+    let err = run(
+        config(ProviderKind::OpenWeather, None),
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        date(),
+        replay("weatherapi_quota_exceeded"),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        WeatherCliError::ProviderError(ProviderError::RequestFailed(_))
+    ));
+}
+
+#[test]
+fn test_record_then_replay() {
+    let dir = std::env::temp_dir().join(format!("weather-cassette-{}", std::process::id()));
+    let body = r#"{"error":{"code":2006,"message":"API key wa-cassette-key is invalid."}}"#;
+    // as Config::load does for configured keys
+    redact::register_secret("wa-cassette-key");
+    let (base_url, _requests) = common::serve(vec![(401, body.to_string())]);
+    let record = HttpSettings {
+        record: Some(dir.clone()),
+        retries: Some(0),
+        ..Default::default()
+    };
+
+    let recorded = run(
+        config(ProviderKind::WeatherApi, Some(base_url.clone())),
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        recent(),
+        record,
+    )
+    .unwrap_err();
+    let contents = fs::read_to_string(dir.join("cassette.json")).unwrap();
+    assert!(!contents.contains("wa-cassette-key"), "{contents}");
+    assert!(contents.contains("key=***"));
+
+    // the stand-in server is gone, replay must not touch the network
+    let replayed = run(
+        config(ProviderKind::WeatherApi, Some(base_url)),
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        recent(),
+        replay_dir(dir.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(recorded.exit_code(), 4);
+    assert_eq!(replayed.exit_code(), 4);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn replay_dir(dir: PathBuf) -> HttpSettings {
+    HttpSettings {
+        replay: Some(dir),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,97 @@
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Canned answer of `serve_with_headers`.
+pub struct Canned {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+/// Request seen by `serve_with_headers`, header names lowercased.
+pub struct Received {
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Received {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Local HTTP stand-in answering each connection with the next canned `(status, body)`.
+/// Returns its base URL and a channel with the request targets it received.
+pub fn serve(responses: Vec<(u16, String)>) -> (String, Receiver<String>) {
+    let responses = responses
+        .into_iter()
+        .map(|(status, body)| Canned {
+            status,
+            headers: Vec::new(),
+            body,
+        })
+        .collect();
+    let (base_url, received) = serve_with_headers(responses);
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for request in received {
+            if tx.send(request.target).is_err() {
+                break;
+            }
+        }
+    });
+    (base_url, rx)
+}
+
+/// Same as `serve`, with response headers and the request headers passed back.
+pub fn serve_with_headers(responses: Vec<Canned>) -> (String, Receiver<Received>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for canned in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+                }
+            }
+            let target = request_line.split_whitespace().nth(1).unwrap_or_default();
+            let _ = tx.send(Received {
+                target: target.to_string(),
+                headers,
+            });
+            let extra: String = canned
+                .headers
+                .iter()
+                .map(|(name, value)| format!("{name}: {value}\r\n"))
+                .collect();
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+                canned.status,
+                canned.body.len(),
+                extra,
+                canned.body
+            )
+            .unwrap();
+        }
+    });
+    (base_url, rx)
+}
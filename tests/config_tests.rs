@@ -1,6 +1,5 @@
 use std::fs;
 use std::path::Path;
-use weather::argparser::ProviderName;
 use weather::configs::{self, Config};
 
 const CONFIG_FILE_NAME: &str = "/tmp/weather_cli_config.toml";
@@ -22,18 +21,43 @@ fn test_config_initial_load() {
 #[test]
 fn test_select_default_provider() {
     cleanup_config_file();
-    configs::select_default_provider(&ProviderName::WeatherApi);
+    // 1. Two instances of the same provider type
+    fs::write(
+        CONFIG_FILE_NAME,
+        r#"
+[providers.ow-work]
+type = "openweather"
+api_key = "work"
+
+[providers.ow-home]
+type = "openweather"
+api_key = "home"
+
+[providers.WeatherApi]
+type = "weatherapi"
+api_key = "wa"
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        configs::select_default_provider("ow-work").unwrap(),
+        "ow-work"
+    );
 
     // 2. Load the config and verify
     let config = Config::load();
-    assert_eq!(config.default_provider, Some(ProviderName::WeatherApi));
+    assert_eq!(config.default_provider.as_deref(), Some("ow-work"));
 
-    // 3. Select a different provider
-    configs::select_default_provider(&ProviderName::OpenWeather);
+    // 3. Select by type, resolved to the instance named after it
+    configs::select_default_provider("weather-api").unwrap();
 
     // 4. Load and verify the change
     let config = Config::load();
-    assert_eq!(config.default_provider, Some(ProviderName::OpenWeather));
+    assert_eq!(config.default_provider.as_deref(), Some("WeatherApi"));
+
+    // 5. Unknown names and ambiguous types are refused
+    assert!(configs::select_default_provider("ow-office").is_err());
+    assert!(configs::select_default_provider("open-weather").is_err());
 
     cleanup_config_file();
 }
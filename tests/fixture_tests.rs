@@ -0,0 +1,93 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use weather::cli::{WeatherCliError, run};
+use weather::configs::{Config, ProviderConfig};
+use weather::http::HttpSettings;
+use weather::providers::WeatherProvider;
+use weather::providers::error::ProviderError;
+use weather::providers::fixture::Fixture;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+fn config(path: Option<PathBuf>) -> Config {
+    Config {
+        default_provider: Some("fixture".to_string()),
+        providers: HashMap::from([(
+            "fixture".to_string(),
+            ProviderConfig::Fixture { path, seed: None },
+        )]),
+        ..Default::default()
+    }
+}
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+#[test]
+fn test_toml_fixture_day() {
+    let provider = Fixture::new(Some(fixture("days.toml")), None);
+    let report = provider.get_weather(50.45, 30.52, date(1, 1)).unwrap();
+    let text = report.to_string();
+    assert!(text.contains("Light snow"));
+    assert!(text.contains("min -4.5°C | max 1.2°C"));
+}
+
+#[test]
+fn test_json_fixture_day() {
+    let provider = Fixture::new(Some(fixture("days.json")), None);
+    let report = provider.get_weather(50.45, 30.52, date(7, 1)).unwrap();
+    assert!(report.to_string().contains("Clear sky"));
+}
+
+#[test]
+fn test_run_with_fixture_file() {
+    let result = run(
+        config(Some(fixture("days.toml"))),
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        date(7, 1),
+        HttpSettings::default(),
+    );
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_run_with_day_missing_from_fixture() {
+    let err = run(
+        config(Some(fixture("days.toml"))),
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        date(3, 15),
+        HttpSettings::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        WeatherCliError::ProviderError(ProviderError::DateIsOutOfRange(_))
+    ));
+    assert_eq!(err.exit_code(), 7);
+}
+
+#[test]
+fn test_run_with_synthetic_data() {
+    let result = run(
+        config(None),
+        Some(-33.87),
+        Some(151.21),
+        None,
+        None,
+        date(3, 15),
+        HttpSettings::default(),
+    );
+    assert!(result.is_ok(), "{result:?}");
+}
@@ -0,0 +1,96 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use weather::cli::{IndicesOptions, show_indices};
+use weather::configs::{Config, ProviderConfig};
+use weather::http::HttpSettings;
+use weather::providers::WeatherProvider;
+use weather::providers::fixture::Fixture;
+use weather::providers::indices::{IndexKind, Level};
+
+fn fixture() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/days.toml")
+}
+
+fn config() -> Config {
+    Config {
+        default_provider: Some("fixture".to_string()),
+        providers: HashMap::from([(
+            "fixture".to_string(),
+            ProviderConfig::Fixture {
+                path: Some(fixture()),
+                seed: None,
+            },
+        )]),
+        ..Default::default()
+    }
+}
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+}
+
+#[test]
+fn test_summer_day_shows_heat_index() {
+    let provider = Fixture::new(Some(fixture()), None);
+    let report = provider.get_weather(50.45, 30.52, date(7, 1)).unwrap();
+    let text = report.to_string();
+    assert!(text.contains("Heat index    : 28.7°C (caution)"), "{text}");
+    assert!(
+        text.contains("Humidex       : 32 (some discomfort)"),
+        "{text}"
+    );
+    assert!(!text.contains("Wind chill"), "{text}");
+}
+
+#[test]
+fn test_selected_indices_only() {
+    let indices = show_indices(
+        config(),
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        IndicesOptions {
+            date: date(7, 1),
+            kinds: vec![IndexKind::HeatIndex, IndexKind::WindChill],
+            json: true,
+        },
+        HttpSettings::default(),
+    )
+    .unwrap();
+    // no wind chill on a summer day
+    assert_eq!(indices.len(), 1, "{indices:?}");
+    assert_eq!(indices[0].level, Level::Low);
+    let json = serde_json::to_value(indices).unwrap();
+    assert_eq!(json[0]["index"], "heat-index");
+    assert_eq!(json[0]["category"], "caution");
+    assert_eq!(json[0]["level"], "low");
+}
+
+#[test]
+fn test_winter_day_has_wind_chill() {
+    let indices = show_indices(
+        config(),
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        IndicesOptions {
+            date: date(1, 1),
+            kinds: Vec::new(),
+            json: false,
+        },
+        HttpSettings::default(),
+    )
+    .unwrap();
+    let kinds: Vec<_> = indices.iter().map(|index| index.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            IndexKind::WindChill,
+            IndexKind::ApparentTemperature,
+            IndexKind::Wbgt
+        ]
+    );
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::time::Duration;
+use weather::cli::{WeatherCliError, run};
+use weather::configs::{Config, ProviderConfig};
+use weather::http::HttpSettings;
+use weather::providers::alerts::WeatherAlert;
+use weather::providers::error::ProviderError;
+use weather::providers::json_api::{JsonApiSpec, JsonFields};
+
+fn config(base_url: &str) -> Config {
+    let mut spec = JsonApiSpec::new(
+        format!("{base_url}/forecast?lat={{lat}}&lon={{lon}}&date={{date}}&key={{key}}"),
+        JsonFields::default(),
+    );
+    spec.api_key = Some("json_api_integration_key".to_string());
+    spec.errors.code = Some("/error".to_string());
+    spec.errors.unauthorized = vec!["bad_key".to_string()];
+    Config {
+        default_provider: Some("feed".to_string()),
+        providers: HashMap::from([("feed".to_string(), ProviderConfig::JsonApi(Box::new(spec)))]),
+        ..Default::default()
+    }
+}
+
+fn weather(config: Config, date: NaiveDate) -> Result<Vec<WeatherAlert>, WeatherCliError> {
+    run(
+        config,
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        date,
+        HttpSettings::default(),
+    )
+}
+
+fn date() -> NaiveDate {
+    chrono::Local::now().naive_local().date()
+}
+
+#[test]
+fn test_json_api_report() {
+    let body = r#"{"min_temp":1,"max_temp":9,"avg_temp":5,"pop":0.2,"summary":"Drizzle",
+        "wind_speed":3,"humidity":80}"#;
+    let (base_url, requests) = common::serve(vec![(200, body.to_string())]);
+
+    assert!(weather(config(&base_url), date()).is_ok());
+
+    let target = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert_eq!(
+        target,
+        format!(
+            "/forecast?lat=50.45&lon=30.52&date={}&key=json_api_integration_key",
+            date()
+        )
+    );
+}
+
+#[test]
+fn test_json_api_error_body() {
+    let (base_url, _requests) = common::serve(vec![(200, r#"{"error":"bad_key"}"#.to_string())]);
+
+    let err = weather(config(&base_url), date()).unwrap_err();
+
+    assert!(matches!(
+        err,
+        WeatherCliError::ProviderError(ProviderError::Unauthorized(_))
+    ));
+    assert_eq!(err.exit_code(), 4);
+}
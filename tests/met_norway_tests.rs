@@ -0,0 +1,129 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::Canned;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use weather::http::{HttpClient, HttpSettings};
+use weather::http_cache::ResponseCache;
+use weather::providers::WeatherProvider;
+use weather::providers::error::ProviderError;
+use weather::providers::met_norway::MetNorway;
+
+const USER_AGENT: &str = "weather-cli-tests/1.0 ops@example.com";
+
+fn forecast() -> String {
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/met_norway_oslo.json");
+    fs::read_to_string(path).unwrap()
+}
+
+fn provider(base_url: String, cache: &Path) -> MetNorway {
+    let client = HttpClient::new(&HttpSettings::default()).unwrap();
+    MetNorway::new(Some(USER_AGENT.to_string()), Some(base_url), client)
+        .with_cache(ResponseCache::with_path(cache.to_path_buf()))
+}
+
+fn cache_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "weather-metno-{}-{}.toml",
+        name,
+        std::process::id()
+    ))
+}
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+}
+
+#[test]
+fn test_daily_report_from_timeseries() {
+    let cache = cache_path("daily");
+    let (base_url, requests) = common::serve_with_headers(vec![Canned {
+        status: 200,
+        headers: Vec::new(),
+        body: forecast(),
+    }]);
+
+    let report = provider(base_url, &cache)
+        .get_weather(59.9139, 10.7522, date())
+        .unwrap()
+        .to_string();
+
+    assert!(report.contains("Light rain"), "{report}");
+    assert!(report.contains("Condition     : light rain"), "{report}");
+    assert!(
+        report.contains("min 12.0°C | max 20.0°C | avg 16.0°C"),
+        "{report}"
+    );
+    assert!(
+        report.contains("4.0 m/sec (Beaufort 3, gentle breeze) from N 0° ↓"),
+        "{report}"
+    );
+    let request = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert_eq!(
+        request.target,
+        "/locationforecast/2.0/complete?lat=59.9139&lon=10.7522"
+    );
+    assert_eq!(request.header("user-agent"), Some(USER_AGENT));
+    let _ = fs::remove_file(cache);
+}
+
+#[test]
+fn test_honours_expires_and_revalidates() {
+    let cache = cache_path("expires");
+    let (base_url, requests) = common::serve_with_headers(vec![
+        Canned {
+            status: 200,
+            headers: vec![
+                ("Expires", "Tue, 01 Jul 2025 06:30:00 GMT".to_string()),
+                ("Last-Modified", "Tue, 01 Jul 2025 05:00:00 GMT".to_string()),
+            ],
+            body: forecast(),
+        },
+        Canned {
+            status: 304,
+            headers: vec![("Cache-Control", "max-age=3600".to_string())],
+            body: String::new(),
+        },
+    ]);
+    let provider = provider(base_url, &cache);
+
+    // already expired: the second call revalidates
+    assert!(provider.get_weather(59.9139, 10.7522, date()).is_ok());
+    assert!(provider.get_weather(59.9139, 10.7522, date()).is_ok());
+    // fresh after the 304: no request at all
+    assert!(provider.get_weather(59.9139, 10.7522, date()).is_ok());
+
+    let first = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert_eq!(first.header("if-modified-since"), None);
+    let second = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert_eq!(
+        second.header("if-modified-since"),
+        Some("Tue, 01 Jul 2025 05:00:00 GMT")
+    );
+    assert!(requests.recv_timeout(Duration::from_millis(200)).is_err());
+    let _ = fs::remove_file(cache);
+}
+
+#[test]
+fn test_day_outside_forecast() {
+    let cache = cache_path("range");
+    let (base_url, _requests) = common::serve_with_headers(vec![Canned {
+        status: 200,
+        headers: Vec::new(),
+        body: forecast(),
+    }]);
+
+    let err = provider(base_url, &cache)
+        .get_weather(
+            59.9139,
+            10.7522,
+            NaiveDate::from_ymd_opt(2025, 7, 5).unwrap(),
+        )
+        .unwrap_err();
+
+    assert!(matches!(err, ProviderError::DateIsOutOfRange(_)));
+    let _ = fs::remove_file(cache);
+}
@@ -0,0 +1,208 @@
+mod common;
+
+use chrono::NaiveDate;
+use common::Canned;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use weather::http::{HttpClient, HttpSettings};
+use weather::http_cache::ResponseCache;
+use weather::providers::WeatherProvider;
+use weather::providers::alerts::AlertSeverity;
+use weather::providers::error::ProviderError;
+use weather::providers::nws::Nws;
+
+const POINT: &str = r#"{"properties":{"gridId":"OKX","gridX":33,"gridY":35,
+    "forecast":"https://api.weather.gov/gridpoints/OKX/33,35/forecast"}}"#;
+
+const FORECAST: &str = r#"{"properties":{"periods":[
+    {"number":1,"name":"Today","startTime":"2025-07-01T06:00:00-04:00","isDaytime":true,
+     "temperature":29,"temperatureUnit":"C","probabilityOfPrecipitation":{"value":40},
+     "shortForecast":"Chance Showers And Thunderstorms"},
+    {"number":2,"name":"Tonight","startTime":"2025-07-01T18:00:00-04:00","isDaytime":false,
+     "temperature":21,"temperatureUnit":"C","probabilityOfPrecipitation":{"value":20},
+     "shortForecast":"Mostly Clear"},
+    {"number":3,"name":"Wednesday","startTime":"2025-07-02T06:00:00-04:00","isDaytime":true,
+     "temperature":31,"temperatureUnit":"C","probabilityOfPrecipitation":{"value":null},
+     "shortForecast":"Sunny"}]}}"#;
+
+const GRID: &str = r#"{"properties":{
+    "temperature":{"uom":"wmoUnit:degC","values":[
+        {"validTime":"2025-07-01T04:00:00+00:00/PT12H","value":24},
+        {"validTime":"2025-07-01T16:00:00+00:00/PT12H","value":26}]},
+    "relativeHumidity":{"uom":"wmoUnit:percent","values":[
+        {"validTime":"2025-07-01T04:00:00+00:00/P1D","value":65}]},
+    "windSpeed":{"uom":"wmoUnit:km_h-1","values":[
+        {"validTime":"2025-07-01T04:00:00+00:00/P1D","value":18}]},
+    "windDirection":{"uom":"wmoUnit:degree_(angle)","values":[
+        {"validTime":"2025-07-01T04:00:00+00:00/P1D","value":225}]}}}"#;
+
+const ALERTS: &str = r#"{"features":[{"properties":{"senderName":"NWS Upton NY","event":"Heat Advisory",
+    "severity":"Moderate","headline":"Heat Advisory issued July 1 at 3:12AM EDT",
+    "onset":"2025-07-01T11:00:00-04:00","ends":"2025-07-01T20:00:00-04:00",
+    "expires":"2025-07-01T20:00:00-04:00","description":"Heat index values up to 105."}}]}"#;
+
+const NO_ALERTS: &str = r#"{"features":[]}"#;
+
+fn ok(body: &str) -> Canned {
+    Canned {
+        status: 200,
+        headers: Vec::new(),
+        body: body.to_string(),
+    }
+}
+
+fn provider(base_url: String, cache: &str) -> (Nws, PathBuf) {
+    let path =
+        std::env::temp_dir().join(format!("weather-nws-{}-{}.toml", cache, std::process::id()));
+    let client = HttpClient::new(&HttpSettings::default()).unwrap();
+    let provider =
+        Nws::new(Some(base_url), client).with_cache(ResponseCache::with_path(path.clone()));
+    (provider, path)
+}
+
+fn date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()
+}
+
+#[test]
+fn test_day_and_night_combined() {
+    let (base_url, requests) =
+        common::serve_with_headers(vec![ok(POINT), ok(FORECAST), ok(GRID), ok(ALERTS)]);
+    let (provider, cache) = provider(base_url, "daily");
+
+    let report = provider
+        .get_weather(40.7128, -74.006, date())
+        .unwrap()
+        .to_string();
+
+    assert!(
+        report.contains("Chance Showers And Thunderstorms, then Mostly Clear"),
+        "{report}"
+    );
+    assert!(
+        report.contains("min 21.0°C | max 29.0°C | avg 25.0°C"),
+        "{report}"
+    );
+    assert!(report.contains("Humidity      : 65.0%"), "{report}");
+    assert!(
+        report.contains("5.0 m/sec (Beaufort 3, gentle breeze) from SW 225° ↗"),
+        "{report}"
+    );
+    assert!(
+        report.starts_with(
+            "ALERT         : Heat Advisory (Moderate) from NWS Upton NY, until 2025-07-01 20:00 -04:00\n"
+        ),
+        "{report}"
+    );
+    let targets: Vec<_> = (0..4)
+        .map(|_| requests.recv_timeout(Duration::from_secs(1)).unwrap())
+        .collect();
+    assert_eq!(targets[0].target, "/points/40.7128,-74.006");
+    assert_eq!(targets[0].header("accept"), Some("application/geo+json"));
+    assert_eq!(targets[1].target, "/gridpoints/OKX/33,35/forecast?units=si");
+    assert_eq!(targets[2].target, "/gridpoints/OKX/33,35");
+    assert_eq!(targets[3].target, "/alerts/active?point=40.7128,-74.006");
+    let _ = fs::remove_file(cache);
+}
+
+#[test]
+fn test_failing_alerts_keep_the_forecast() {
+    let down = Canned {
+        status: 503,
+        headers: Vec::new(),
+        body: "Service Unavailable".to_string(),
+    };
+    let (base_url, _requests) =
+        common::serve_with_headers(vec![ok(POINT), ok(FORECAST), ok(GRID), down]);
+    let (provider, cache) = provider(base_url, "alerts-down");
+
+    let report = provider.get_weather(40.7128, -74.006, date()).unwrap();
+
+    assert!(report.alerts().is_empty());
+    assert!(
+        report.to_string().contains("min 21.0°C | max 29.0°C"),
+        "{report}"
+    );
+    let _ = fs::remove_file(cache);
+}
+
+#[test]
+fn test_grid_lookup_is_cached() {
+    let (base_url, requests) = common::serve_with_headers(vec![
+        ok(POINT),
+        ok(FORECAST),
+        ok(GRID),
+        ok(NO_ALERTS),
+        ok(FORECAST),
+        ok(GRID),
+        ok(NO_ALERTS),
+    ]);
+    let (provider, cache) = provider(base_url, "grid");
+
+    assert!(provider.get_weather(40.7128, -74.006, date()).is_ok());
+    assert!(provider.get_weather(40.7128, -74.006, date()).is_ok());
+
+    let targets: Vec<_> = (0..7)
+        .map(|_| {
+            requests
+                .recv_timeout(Duration::from_secs(1))
+                .unwrap()
+                .target
+        })
+        .collect();
+    assert_eq!(
+        targets
+            .iter()
+            .filter(|target| target.starts_with("/points"))
+            .count(),
+        1
+    );
+    let _ = fs::remove_file(cache);
+}
+
+#[test]
+fn test_point_outside_us() {
+    let (base_url, _requests) = common::serve_with_headers(vec![Canned {
+        status: 404,
+        headers: Vec::new(),
+        body: r#"{"type":"https://api.weather.gov/problems/InvalidPoint","title":"Invalid Point",
+            "status":404,"detail":"Unable to provide data for requested point 50.45,30.52"}"#
+            .to_string(),
+    }]);
+    let (provider, cache) = provider(base_url, "invalid");
+
+    let err = provider.get_weather(50.45, 30.52, date()).unwrap_err();
+
+    assert!(matches!(err, ProviderError::NotFound(_)));
+    assert_eq!(err.exit_code(), 6);
+    let _ = fs::remove_file(cache);
+}
+
+#[test]
+fn test_active_alerts() {
+    let (base_url, requests) = common::serve_with_headers(vec![ok(ALERTS)]);
+    let (provider, cache) = provider(base_url, "alerts");
+
+    let alerts = provider.get_alerts(40.7128, -74.006).unwrap();
+
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].severity, AlertSeverity::Moderate);
+    let text = alerts[0].to_string();
+    assert!(
+        text.starts_with("Heat Advisory (Moderate) from NWS Upton NY"),
+        "{text}"
+    );
+    assert!(
+        text.contains("2025-07-01 11:00 -04:00 to 2025-07-01 20:00 -04:00"),
+        "{text}"
+    );
+    assert_eq!(
+        requests
+            .recv_timeout(Duration::from_secs(1))
+            .unwrap()
+            .target,
+        "/alerts/active?point=40.7128,-74.006"
+    );
+    let _ = fs::remove_file(cache);
+}
@@ -0,0 +1,67 @@
+use chrono::NaiveDate;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use weather::cli::{WeatherCliError, run};
+use weather::configs::{Config, ProviderConfig};
+use weather::http::HttpSettings;
+use weather::providers::alerts::WeatherAlert;
+use weather::providers::error::ProviderError;
+
+fn config(options: BTreeMap<String, Value>) -> Config {
+    let script = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/plugins/static_report.sh");
+    Config {
+        default_provider: Some("feed".to_string()),
+        providers: HashMap::from([(
+            "feed".to_string(),
+            ProviderConfig::Plugin {
+                command: PathBuf::from("sh"),
+                args: vec![script.display().to_string()],
+                options,
+                timeout_secs: Some(10),
+                forecast_days: Some(3),
+                history_days: Some(0),
+            },
+        )]),
+        ..Default::default()
+    }
+}
+
+fn weather(config: Config, date: NaiveDate) -> Result<Vec<WeatherAlert>, WeatherCliError> {
+    run(
+        config,
+        Some(50.45),
+        Some(30.52),
+        None,
+        None,
+        date,
+        HttpSettings::default(),
+    )
+}
+
+fn today() -> NaiveDate {
+    chrono::Local::now().naive_local().date()
+}
+
+#[test]
+fn test_plugin_report() {
+    assert!(weather(config(BTreeMap::new()), today()).is_ok());
+}
+
+#[test]
+fn test_plugin_error_kind_sets_exit_code() {
+    let options = BTreeMap::from([("fail".to_string(), Value::Bool(true))]);
+    let err = weather(config(options), today()).unwrap_err();
+    assert!(matches!(
+        err,
+        WeatherCliError::ProviderError(ProviderError::NotFound(_))
+    ));
+    assert_eq!(err.exit_code(), 6);
+}
+
+#[test]
+fn test_plugin_capabilities_from_config() {
+    let past = today().pred_opt().unwrap();
+    let err = weather(config(BTreeMap::new()), past).unwrap_err();
+    assert_eq!(err.exit_code(), 7);
+}
@@ -0,0 +1,13 @@
+#!/bin/sh
+# Minimal weather-cli plugin (protocol 1): answers every request with the same day,
+# or with a "not_found" error when the "fail" option is set.
+request=$(cat)
+case "$request" in
+*'"fail":true'*)
+    echo '{"protocol":1,"error":{"kind":"not_found","message":"no station near the location"}}'
+    ;;
+*)
+    date=$(printf '%s' "$request" | sed 's/.*"date":"\([0-9-]*\)".*/\1/')
+    printf '{"protocol":1,"report":{"min_temp":3.5,"max_temp":11.0,"avg_temp":7.2,"pop":0.4,"summary":"Showers on %s","wind_speed":4.1,"humidity":77.0,"wind_direction":250}}\n' "$date"
+    ;;
+esac
@@ -0,0 +1,41 @@
+mod common;
+
+use chrono::Local;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use weather::http::{HttpClient, HttpSettings};
+use weather::providers::WeatherProvider;
+use weather::providers::error::ProviderError;
+use weather::providers::open_weather::OpenWeather;
+use weather::quota::{QuotaLimits, QuotaMeter};
+
+#[test]
+fn test_local_limit_refuses_before_request() {
+    let path = std::env::temp_dir().join(format!("weather-quota-test-{}.toml", std::process::id()));
+    let (base_url, requests) = common::serve(vec![(200, "{}".to_string())]);
+    let limits = QuotaLimits {
+        daily: Some(0),
+        ..Default::default()
+    };
+    let client = HttpClient::new(&HttpSettings::default())
+        .unwrap()
+        .with_meter(Arc::new(QuotaMeter::with_path(
+            "ow-work",
+            "default",
+            "ow_key",
+            limits,
+            path.clone(),
+        )));
+    let provider = OpenWeather::new("ow_key".to_string(), Some(base_url), client);
+
+    let err = provider
+        .get_weather(50.45, 30.52, Local::now().naive_local().date())
+        .unwrap_err();
+
+    assert!(matches!(err, ProviderError::LocalQuotaExceeded(_)));
+    assert_eq!(err.exit_code(), 5);
+    assert!(!err.to_string().contains("ow_key"));
+    assert!(requests.recv_timeout(Duration::from_millis(200)).is_err());
+    let _ = fs::remove_file(path);
+}
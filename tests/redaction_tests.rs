@@ -0,0 +1,70 @@
+use reqwest::header::HeaderMap;
+use weather::cli::WeatherCliError;
+use weather::configs::ProviderConfig;
+use weather::providers::error::{HttpFailure, ProviderError};
+use weather::redact;
+
+const KEY: &str = "ow-4f1c9e2b7d";
+
+fn assert_no_leak(err: WeatherCliError) {
+    let msg = err.to_string();
+    assert!(!msg.contains(KEY), "key leaked: {msg}");
+}
+
+#[test]
+fn test_request_failure_url_does_not_leak_key() {
+    redact::register_secret(KEY);
+    assert_no_leak(WeatherCliError::from(ProviderError::RequestFailed(
+        format!(
+            "error sending request for url (https://api.openweathermap.org/data/3.0/onecall?lat=1&lon=2&appid={KEY}&units=metric)"
+        ),
+    )));
+}
+
+#[test]
+fn test_provider_message_does_not_leak_key() {
+    redact::register_secret(KEY);
+    let failure = HttpFailure::new(
+        401,
+        &HeaderMap::new(),
+        Some("401".to_string()),
+        format!("Invalid API key {KEY}"),
+    );
+    assert_no_leak(WeatherCliError::from(ProviderError::Unauthorized(failure)));
+}
+
+#[test]
+fn test_unregistered_key_in_query_does_not_leak() {
+    let msg = WeatherCliError::from(ProviderError::ParseError(
+        "https://api.weatherapi.com/v1/forecast.json?q=1&key=wa-unregistered".to_string(),
+    ))
+    .to_string();
+    assert!(!msg.contains("wa-unregistered"), "key leaked: {msg}");
+}
+
+#[test]
+fn test_provider_config_debug_does_not_leak_key() {
+    let config = ProviderConfig::OpenWeather {
+        api_key: KEY.to_string(),
+        api_keys: Vec::new(),
+        base_url: None,
+    };
+    assert!(!format!("{config:?}").contains(KEY));
+}
+
+#[test]
+fn test_pooled_keys_debug_shows_labels_only() {
+    let config: ProviderConfig = toml::from_str(&format!(
+        r#"
+        type = "weatherapi"
+        api_key = "{KEY}"
+        api_keys = [{{ label = "team-b", key = "{KEY}-b" }}]
+        "#
+    ))
+    .unwrap();
+    let debug = format!("{config:?}");
+    assert!(debug.contains("team-b"));
+    assert!(!debug.contains(KEY), "key leaked: {debug}");
+    let labels: Vec<_> = config.api_keys().into_iter().map(|k| k.label).collect();
+    assert_eq!(labels, ["default", "team-b"]);
+}
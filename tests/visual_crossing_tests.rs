@@ -0,0 +1,140 @@
+mod common;
+
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::time::Duration;
+use weather::cli::{WeatherCliError, run_range};
+use weather::configs::{Config, ProviderConfig};
+use weather::http::{HttpClient, HttpSettings};
+use weather::providers::WeatherProvider;
+use weather::providers::error::ProviderError;
+use weather::providers::visual_crossing::VisualCrossing;
+
+const TIMELINE: &str = r#"{"queryCost":3,"resolvedAddress":"51.5,-0.1","days":[
+    {"datetime":"1995-08-01","tempmax":27.4,"tempmin":15.1,"temp":21.0,"humidity":58.2,
+     "precip":0.0,"precipprob":0,"windspeed":14.4,"winddir":250.0,"conditions":"Clear",
+     "normal":{"tempmax":[19.0,23.1,31.2],"tempmin":[9.8,14.0,18.5],"precip":[0.0,1.6,22.0],
+               "humidity":[48.0,71.3,92.0],"windspeed":[5.0,15.1,40.0]}},
+    {"datetime":"1995-08-02","tempmax":29.0,"tempmin":16.3,"temp":22.4,"humidity":55.0,
+     "precip":0.0,"precipprob":0,"windspeed":10.8,"winddir":200.0,"conditions":"Partially cloudy",
+     "normal":{"tempmax":[18.5,23.0,30.9],"tempmin":[10.1,14.1,18.0]}},
+    {"datetime":"1995-08-03","tempmax":24.2,"tempmin":14.0,"temp":18.9,"humidity":80.1,
+     "precip":6.2,"precipprob":100,"windspeed":21.6,"winddir":270.0,"conditions":"Rain, Overcast"}]}"#;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(1995, 8, day).unwrap()
+}
+
+#[test]
+fn test_range_in_one_request() {
+    let (base_url, requests) = common::serve(vec![(200, TIMELINE.to_string())]);
+    let client = HttpClient::new(&HttpSettings::default()).unwrap();
+    let provider = VisualCrossing::new("vc-range-key".to_string(), Some(base_url), client);
+
+    let days = provider
+        .get_weather_range(51.5, -0.1, date(1), date(3))
+        .unwrap();
+
+    assert_eq!(
+        days.iter().map(|(day, _)| *day).collect::<Vec<_>>(),
+        vec![date(1), date(2), date(3)]
+    );
+    let first = days[0].1.to_string();
+    assert!(
+        first.contains("max 4.0 m/sec (Beaufort 3, gentle breeze) from WSW 250° →"),
+        "{first}"
+    );
+    assert!(first.contains("Normals"), "{first}");
+    let normals = days[0].1.normals().unwrap();
+    assert_eq!(normals.max_temp.unwrap().mean, 23.1);
+    assert!(days[2].1.normals().is_none());
+    let target = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert!(
+        target.starts_with("/timeline/51.5%2C-0.1/1995-08-01/1995-08-03?"),
+        "{target}"
+    );
+    assert!(requests.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn test_run_range_through_config() {
+    let (base_url, requests) = common::serve(vec![(200, TIMELINE.to_string())]);
+    let config = Config {
+        default_provider: Some("vc".to_string()),
+        providers: HashMap::from([(
+            "vc".to_string(),
+            ProviderConfig::VisualCrossing {
+                api_key: "vc-config-key".to_string(),
+                api_keys: Vec::new(),
+                base_url: Some(base_url),
+            },
+        )]),
+        ..Default::default()
+    };
+
+    let result = run_range(
+        config,
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        date(1)..=date(3),
+        HttpSettings::default(),
+    );
+
+    assert!(result.is_ok(), "{result:?}");
+    assert!(requests.recv_timeout(Duration::from_secs(1)).is_ok());
+    assert!(requests.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn test_inverted_range_is_rejected_before_any_request() {
+    let (base_url, requests) = common::serve(vec![(200, TIMELINE.to_string())]);
+    let config = Config {
+        default_provider: Some("vc".to_string()),
+        providers: HashMap::from([(
+            "vc".to_string(),
+            ProviderConfig::VisualCrossing {
+                api_key: "vc-config-key".to_string(),
+                api_keys: Vec::new(),
+                base_url: Some(base_url),
+            },
+        )]),
+        ..Default::default()
+    };
+
+    let err = run_range(
+        config,
+        Some(51.5),
+        Some(-0.1),
+        None,
+        None,
+        date(3)..=date(1),
+        HttpSettings::default(),
+    )
+    .unwrap_err();
+
+    assert!(
+        matches!(
+            err,
+            WeatherCliError::ProviderError(ProviderError::DateIsOutOfRange(_))
+        ),
+        "{err}"
+    );
+    assert_eq!(err.exit_code(), 7);
+    assert!(requests.recv_timeout(Duration::from_millis(200)).is_err());
+}
+
+#[test]
+fn test_invalid_key() {
+    let (base_url, _requests) = common::serve(vec![(
+        401,
+        "No account found with API key 'vc-wrong-key'".to_string(),
+    )]);
+    let client = HttpClient::new(&HttpSettings::default()).unwrap();
+    let provider = VisualCrossing::new("vc-wrong-key".to_string(), Some(base_url), client);
+
+    let err = provider.get_weather(51.5, -0.1, date(1)).unwrap_err();
+
+    assert!(matches!(err, ProviderError::Unauthorized(_)));
+}